@@ -0,0 +1,137 @@
+//! Compares `VastEnum::is_valid` and `VastEnum::variant` through the derived validity table against
+//! num_enum's generated `match`, and the bulk slice checks against checking one value at a time.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use vast_enum::{VastEnum, VastSlice};
+
+macro_rules! opcodes {
+    ($name:ident, $repr:ty, $($derive:ident),+) => {
+        #[allow(dead_code)]
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, $($derive),+)]
+        #[repr($repr)]
+        enum $name {
+            V0 = 0x00, V1 = 0x03, V2 = 0x07, V3 = 0x0A, V4 = 0x11, V5 = 0x14, V6 = 0x19, V7 = 0x1C,
+            V8 = 0x20, V9 = 0x26, V10 = 0x2B, V11 = 0x31, V12 = 0x35, V13 = 0x3A, V14 = 0x40,
+            V15 = 0x44, V16 = 0x4B, V17 = 0x50, V18 = 0x57, V19 = 0x5D, V20 = 0x61, V21 = 0x68,
+            V22 = 0x6E, V23 = 0x73, V24 = 0x79, V25 = 0x80, V26 = 0x88, V27 = 0x91, V28 = 0x9C,
+            V29 = 0xA5, V30 = 0xB0, V31 = 0xBB, V32 = 0xC6, V33 = 0xD1, V34 = 0xE0, V35 = 0xF3,
+        }
+    };
+}
+
+opcodes!(NumEnumU8, u8, IntoPrimitive, TryFromPrimitive);
+opcodes!(TableU8, u8, VastEnum);
+
+macro_rules! sparse {
+    ($name:ident, $($derive:ident),+) => {
+        #[allow(dead_code)]
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, $($derive),+)]
+        #[repr(u32)]
+        enum $name {
+            V0 = 0x0000_0001, V1 = 0x0000_1000, V2 = 0x0002_0000, V3 = 0x0030_0000,
+            V4 = 0x0400_0000, V5 = 0x5000_0000, V6 = 0x6000_0001, V7 = 0x7000_0010,
+            V8 = 0x8000_0100, V9 = 0x9000_1000, V10 = 0xA001_0000, V11 = 0xB010_0000,
+            V12 = 0xC100_0000, V13 = 0xD000_0002, V14 = 0xE000_0020, V15 = 0xF000_0200,
+        }
+    };
+}
+
+sparse!(NumEnumU32, IntoPrimitive, TryFromPrimitive);
+sparse!(TableU32, VastEnum);
+
+/// Deterministic pseudo-random discriminants, so both paths see the same inputs.
+fn inputs<T>(len: usize, f: impl Fn(u64) -> T) -> Vec<T> {
+    let mut state = 0x2545_F491_4F6C_DD1D_u64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            f(state)
+        })
+        .collect()
+}
+
+fn count_valid<Enum, Repr>(values: &[VastEnum<Enum, Repr>]) -> usize
+where
+    Enum: Into<Repr>,
+    Repr: vast_enum::EnumRepr<Enum>,
+{
+    values.iter().filter(|value| value.is_valid()).count()
+}
+
+fn count_variants<Enum, Repr>(values: &[VastEnum<Enum, Repr>]) -> usize
+where
+    Enum: Into<Repr>,
+    Repr: vast_enum::EnumRepr<Enum>,
+{
+    values
+        .iter()
+        .filter_map(|value| black_box(value.variant()))
+        .count()
+}
+
+fn bench_u8(c: &mut Criterion) {
+    let ints = inputs(4096, |x| x as u8);
+    let num_enum: Vec<VastEnum<NumEnumU8, u8>> =
+        ints.iter().map(|&i| VastEnum::from_int(i)).collect();
+    let table: Vec<VastEnum<TableU8, u8>> = ints.iter().map(|&i| VastEnum::from_int(i)).collect();
+
+    let mut group = c.benchmark_group("is_valid/u8");
+    group.bench_function("num_enum", |b| b.iter(|| count_valid(black_box(&num_enum))));
+    group.bench_function("table", |b| b.iter(|| count_valid(black_box(&table))));
+    group.finish();
+
+    let mut group = c.benchmark_group("variant/u8");
+    group.bench_function("num_enum", |b| {
+        b.iter(|| count_variants(black_box(&num_enum)))
+    });
+    group.bench_function("table", |b| b.iter(|| count_variants(black_box(&table))));
+    group.finish();
+}
+
+fn bench_u32(c: &mut Criterion) {
+    let ints = inputs(4096, |x| {
+        // Mix in valid values, since random `u32`s almost never are.
+        if x & 1 == 0 {
+            TableU32::V9 as u32
+        } else {
+            x as u32
+        }
+    });
+    let num_enum: Vec<VastEnum<NumEnumU32, u32>> =
+        ints.iter().map(|&i| VastEnum::from_int(i)).collect();
+    let table: Vec<VastEnum<TableU32, u32>> = ints.iter().map(|&i| VastEnum::from_int(i)).collect();
+
+    let mut group = c.benchmark_group("is_valid/u32");
+    group.bench_function("num_enum", |b| b.iter(|| count_valid(black_box(&num_enum))));
+    group.bench_function("table", |b| b.iter(|| count_valid(black_box(&table))));
+    group.finish();
+
+    let mut group = c.benchmark_group("variant/u32");
+    group.bench_function("num_enum", |b| {
+        b.iter(|| count_variants(black_box(&num_enum)))
+    });
+    group.bench_function("table", |b| b.iter(|| count_variants(black_box(&table))));
+    group.finish();
+}
+
+fn bench_bulk(c: &mut Criterion) {
+    let table: Vec<VastEnum<TableU8, u8>> = inputs(1 << 20, |x| VastEnum::from_int(x as u8));
+
+    let mut group = c.benchmark_group("count_invalid/u8");
+    group.bench_function("per_value", |b| {
+        b.iter(|| {
+            black_box(&table)
+                .iter()
+                .filter(|value| !value.is_valid())
+                .count()
+        })
+    });
+    group.bench_function("bulk", |b| b.iter(|| black_box(&table[..]).count_invalid()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_u8, bench_u32, bench_bulk);
+criterion_main!(benches);
@@ -0,0 +1,192 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::str::FromStr;
+
+/// A byte that's formatted as an ASCII character, for use as a [`VastEnum`](crate::VastEnum) repr
+/// in text protocols whose tags are single characters.
+///
+/// Any byte can be stored, so non-ASCII bytes read from the wire are kept as unknown
+/// discriminants. They're formatted with escapes, like `'\xff'`.
+///
+/// `#[derive(VastEnum)]` uses this repr for enums whose variants are declared with byte literals,
+/// such as `#[vast_enum(value = b'A')]`. Such byte literals must be ASCII.
+///
+/// This struct has the same in-memory representation as `u8`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{AsciiByte, VastEnum};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// enum Side {
+///     #[vast_enum(value = b'1')]
+///     Buy,
+///     #[vast_enum(value = b'2')]
+///     Sell,
+/// }
+///
+/// let side = VastSide::from_int(AsciiByte::new(b'2'));
+/// assert_eq!(side.variant(), Some(Side::Sell));
+/// assert_eq!(format!("{:?}", side), "VastEnum('2': Sell)");
+/// assert_eq!(side.to_string(), "2");
+///
+/// let side = VastSide::from_int(AsciiByte::new(0xFF));
+/// assert_eq!(format!("{:?}", side), r"VastEnum('\xff')");
+/// assert_eq!(side.to_string().parse(), Ok(AsciiByte::new(0xFF)));
+/// # }
+/// ```
+#[repr(transparent)]
+#[derive(Copy, Clone, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable,
+        zerocopy::Unaligned
+    )
+)]
+pub struct AsciiByte(u8);
+
+impl AsciiByte {
+    /// Creates an [`AsciiByte`] from a byte.
+    pub const fn new(byte: u8) -> Self {
+        AsciiByte(byte)
+    }
+
+    /// Returns the byte.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether the byte is an ASCII character.
+    pub const fn is_ascii(self) -> bool {
+        self.0.is_ascii()
+    }
+
+    /// Returns the byte as a `char`, if it's an ASCII character.
+    pub fn to_char(self) -> Option<char> {
+        if self.is_ascii() {
+            Some(char::from(self.0))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<u8> for AsciiByte {
+    fn from(byte: u8) -> Self {
+        AsciiByte(byte)
+    }
+}
+
+impl From<AsciiByte> for u8 {
+    fn from(byte: AsciiByte) -> Self {
+        byte.0
+    }
+}
+
+/// Formats the byte like a character literal, with escapes for non-printable and non-ASCII bytes.
+impl Debug for AsciiByte {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_char('\'')?;
+        write_escaped(f, self.0, false)?;
+        f.write_char('\'')
+    }
+}
+
+/// Formats the byte as a character, with escapes for backslashes and non-printable and non-ASCII
+/// bytes.
+impl Display for AsciiByte {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write_escaped(f, self.0, true)
+    }
+}
+
+/// Parses a character in the format of the `Display` impl, such as `A`, `\\` or `\xff`.
+impl FromStr for AsciiByte {
+    type Err = ParseAsciiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut byte = [0];
+        parse_escaped(s, &mut byte)?;
+        Ok(AsciiByte(byte[0]))
+    }
+}
+
+// SAFETY: `AsciiByte` is `repr(transparent)` over `u8`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for AsciiByte {}
+
+// SAFETY: `AsciiByte` is `repr(transparent)` over `u8`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for AsciiByte {}
+
+/// Writes `byte` as a character, escaping backslashes and non-printable and non-ASCII bytes. Single
+/// quotes are only escaped when `raw` is `false`, as in a character literal.
+pub(crate) fn write_escaped(f: &mut Formatter<'_>, byte: u8, raw: bool) -> core::fmt::Result {
+    if byte == b'"' || (raw && byte == b'\'') {
+        return f.write_char(char::from(byte));
+    }
+
+    for c in core::ascii::escape_default(byte) {
+        f.write_char(char::from(c))?;
+    }
+
+    Ok(())
+}
+
+/// Parses exactly `bytes.len()` characters written by [`write_escaped`] into `bytes`.
+pub(crate) fn parse_escaped(s: &str, bytes: &mut [u8]) -> Result<(), ParseAsciiError> {
+    let mut rest = s.as_bytes();
+    for byte in bytes {
+        let (parsed, len) = match rest {
+            [b'\\', b'x', high, low, ..] => ((hex_digit(*high)? << 4) | hex_digit(*low)?, 4),
+            [b'\\', escaped, ..] => match escaped {
+                b't' => (b'\t', 2),
+                b'r' => (b'\r', 2),
+                b'n' => (b'\n', 2),
+                b'\'' | b'"' | b'\\' => (*escaped, 2),
+                _ => return Err(ParseAsciiError(())),
+            },
+            [c, ..] if c.is_ascii() => (*c, 1),
+            _ => return Err(ParseAsciiError(())),
+        };
+        *byte = parsed;
+        rest = &rest[len..];
+    }
+
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(ParseAsciiError(()))
+    }
+}
+
+fn hex_digit(c: u8) -> Result<u8, ParseAsciiError> {
+    match char::from(c).to_digit(16) {
+        Some(digit) => Ok(digit as u8),
+        None => Err(ParseAsciiError(())),
+    }
+}
+
+/// The error returned when parsing an [`AsciiByte`] or a [`ByteTag`](crate::ByteTag) from text
+/// fails, because it has an invalid escape, a non-ASCII character or the wrong length.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ParseAsciiError(());
+
+impl Display for ParseAsciiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid escaped ASCII text")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseAsciiError {}
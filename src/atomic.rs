@@ -0,0 +1,380 @@
+use crate::{EnumRepr, VastEnum, VastRepr};
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::sync::atomic::Ordering;
+
+/// A [`VastEnum`] that can be shared between threads, backed by the matching atomic integer type
+/// from [`core::sync::atomic`].
+///
+/// This struct has the same in-memory representation as `Repr`'s atomic type, such as
+/// [`AtomicU8`](core::sync::atomic::AtomicU8) for `u8`. Every operation has a variant that takes
+/// or returns a [`VastEnum`], so unknown discriminants are stored and loaded like any other.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use core::sync::atomic::Ordering;
+/// use vast_enum::{AtomicVastEnum, VastEnum};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum State {
+///     Idle = 0,
+///     Connecting = 1,
+///     Connected = 2,
+/// }
+///
+/// let state = AtomicVastEnum::from_variant(State::Idle);
+///
+/// assert_eq!(
+///     state.compare_exchange_variant(
+///         State::Idle,
+///         State::Connecting,
+///         Ordering::AcqRel,
+///         Ordering::Acquire,
+///     ),
+///     Ok(VastEnum::from_variant(State::Idle))
+/// );
+/// assert_eq!(state.load_variant(Ordering::Acquire), Some(State::Connecting));
+///
+/// state.store(VastEnum::from_int(9), Ordering::Release);
+/// assert_eq!(state.load(Ordering::Acquire).int(), 9);
+/// assert_eq!(state.load_variant(Ordering::Acquire), None);
+/// # }
+/// ```
+#[repr(transparent)]
+pub struct AtomicVastEnum<Enum, Repr = <Enum as VastRepr>::Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + AtomicRepr,
+{
+    atomic: Repr::Atomic,
+    phantom: PhantomData<Enum>,
+}
+
+impl<Enum, Repr> AtomicVastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + AtomicRepr,
+{
+    /// Creates an [`AtomicVastEnum`] holding `value`.
+    pub fn new(value: VastEnum<Enum, Repr>) -> Self {
+        AtomicVastEnum {
+            atomic: Repr::new_atomic(value.int()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates an [`AtomicVastEnum`] holding an enum variant.
+    pub fn from_variant(variant: Enum) -> Self {
+        Self::new(VastEnum::from_variant(variant))
+    }
+
+    /// Returns a mutable reference to the value, which is safe because the mutable reference
+    /// guarantees that no other threads are accessing it.
+    pub fn get_mut(&mut self) -> &mut VastEnum<Enum, Repr> {
+        VastEnum::from_int_mut(Repr::get_mut(&mut self.atomic))
+    }
+
+    /// Consumes the atomic and returns the value.
+    pub fn into_inner(self) -> VastEnum<Enum, Repr> {
+        VastEnum::from_int(Repr::into_inner(self.atomic))
+    }
+
+    /// Loads the value.
+    pub fn load(&self, order: Ordering) -> VastEnum<Enum, Repr> {
+        VastEnum::from_int(Repr::load(&self.atomic, order))
+    }
+
+    /// Loads the value and converts it to the enum type, if it's a valid discriminant.
+    pub fn load_variant(&self, order: Ordering) -> Option<Enum> {
+        self.load(order).variant()
+    }
+
+    /// Stores `value`.
+    pub fn store(&self, value: VastEnum<Enum, Repr>, order: Ordering) {
+        Repr::store(&self.atomic, value.int(), order)
+    }
+
+    /// Stores an enum variant.
+    pub fn store_variant(&self, variant: Enum, order: Ordering) {
+        self.store(VastEnum::from_variant(variant), order)
+    }
+
+    /// Stores `value`, returning the previous value.
+    pub fn swap(&self, value: VastEnum<Enum, Repr>, order: Ordering) -> VastEnum<Enum, Repr> {
+        VastEnum::from_int(Repr::swap(&self.atomic, value.int(), order))
+    }
+
+    /// Stores an enum variant, returning the previous value.
+    pub fn swap_variant(&self, variant: Enum, order: Ordering) -> VastEnum<Enum, Repr> {
+        self.swap(VastEnum::from_variant(variant), order)
+    }
+
+    /// Stores `new` if the current value is `current`.
+    ///
+    /// Returns the previous value, wrapped in `Ok` if it was `current` and in `Err` otherwise. See
+    /// [`AtomicU8::compare_exchange`](core::sync::atomic::AtomicU8::compare_exchange) for the
+    /// meaning of the orderings.
+    pub fn compare_exchange(
+        &self,
+        current: VastEnum<Enum, Repr>,
+        new: VastEnum<Enum, Repr>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<VastEnum<Enum, Repr>, VastEnum<Enum, Repr>> {
+        Repr::compare_exchange(&self.atomic, current.int(), new.int(), success, failure)
+            .map(VastEnum::from_int)
+            .map_err(VastEnum::from_int)
+    }
+
+    /// Stores the variant `new` if the current value is the variant `current`.
+    ///
+    /// Works like [`compare_exchange`](AtomicVastEnum::compare_exchange).
+    pub fn compare_exchange_variant(
+        &self,
+        current: Enum,
+        new: Enum,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<VastEnum<Enum, Repr>, VastEnum<Enum, Repr>> {
+        self.compare_exchange(
+            VastEnum::from_variant(current),
+            VastEnum::from_variant(new),
+            success,
+            failure,
+        )
+    }
+
+    /// Stores `new` if the current value is `current`, but may fail spuriously, which allows more
+    /// efficient code on some platforms when called in a loop.
+    ///
+    /// Otherwise works like [`compare_exchange`](AtomicVastEnum::compare_exchange).
+    pub fn compare_exchange_weak(
+        &self,
+        current: VastEnum<Enum, Repr>,
+        new: VastEnum<Enum, Repr>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<VastEnum<Enum, Repr>, VastEnum<Enum, Repr>> {
+        Repr::compare_exchange_weak(&self.atomic, current.int(), new.int(), success, failure)
+            .map(VastEnum::from_int)
+            .map_err(VastEnum::from_int)
+    }
+
+    /// Repeatedly applies `f` to the current value and tries to store the result, until `f`
+    /// returns `None` or the store succeeds.
+    ///
+    /// Returns the previous value, wrapped in `Ok` if a new value was stored and in `Err`
+    /// otherwise. A typical use is a state machine transition that's only allowed from certain
+    /// states:
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")]
+    /// # {
+    /// use core::sync::atomic::Ordering;
+    /// use vast_enum::{AtomicVastEnum, VastEnum};
+    ///
+    /// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    /// #[repr(u8)]
+    /// enum State {
+    ///     Idle = 0,
+    ///     Connecting = 1,
+    ///     Connected = 2,
+    /// }
+    ///
+    /// let state = AtomicVastEnum::from_variant(State::Connecting);
+    /// let connect = |state: VastState| match state.variant() {
+    ///     Some(State::Connecting) => Some(VastEnum::from_variant(State::Connected)),
+    ///     _ => None,
+    /// };
+    ///
+    /// assert!(state.fetch_update(Ordering::AcqRel, Ordering::Acquire, connect).is_ok());
+    /// assert!(state.fetch_update(Ordering::AcqRel, Ordering::Acquire, connect).is_err());
+    /// assert_eq!(state.load_variant(Ordering::Acquire), Some(State::Connected));
+    /// # }
+    /// ```
+    pub fn fetch_update(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: impl FnMut(VastEnum<Enum, Repr>) -> Option<VastEnum<Enum, Repr>>,
+    ) -> Result<VastEnum<Enum, Repr>, VastEnum<Enum, Repr>> {
+        Repr::fetch_update(&self.atomic, set_order, fetch_order, |int| {
+            f(VastEnum::from_int(int)).map(VastEnum::int)
+        })
+        .map(VastEnum::from_int)
+        .map_err(VastEnum::from_int)
+    }
+}
+
+impl<Enum, Repr> Default for AtomicVastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + AtomicRepr + Default,
+{
+    fn default() -> Self {
+        Self::new(VastEnum::default())
+    }
+}
+
+impl<Enum, Repr> From<VastEnum<Enum, Repr>> for AtomicVastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + AtomicRepr,
+{
+    fn from(value: VastEnum<Enum, Repr>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<Enum, Repr> From<Enum> for AtomicVastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + AtomicRepr,
+{
+    fn from(variant: Enum) -> Self {
+        Self::from_variant(variant)
+    }
+}
+
+impl<Enum, Repr> Debug for AtomicVastEnum<Enum, Repr>
+where
+    Enum: Debug + Into<Repr>,
+    Repr: Debug + EnumRepr<Enum> + AtomicRepr,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
+
+/// A primitive integer with a matching atomic type in [`core::sync::atomic`].
+///
+/// This is implemented for the integer types that have atomics on the target platform.
+pub trait AtomicRepr: Sized {
+    /// The atomic type, such as [`AtomicU8`](core::sync::atomic::AtomicU8) for `u8`.
+    type Atomic: Send + Sync;
+
+    /// Creates an atomic holding `value`.
+    fn new_atomic(value: Self) -> Self::Atomic;
+
+    /// Returns a mutable reference to the atomic's value.
+    fn get_mut(atomic: &mut Self::Atomic) -> &mut Self;
+
+    /// Consumes the atomic and returns its value.
+    fn into_inner(atomic: Self::Atomic) -> Self;
+
+    /// Loads the atomic's value.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Stores `value` in the atomic.
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+
+    /// Stores `value` in the atomic, returning the previous value.
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Stores `new` in the atomic if its value is `current`.
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+
+    /// Stores `new` in the atomic if its value is `current`, but may fail spuriously.
+    fn compare_exchange_weak(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+
+    /// Repeatedly applies `f` to the atomic's value and tries to store the result.
+    fn fetch_update(
+        atomic: &Self::Atomic,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: impl FnMut(Self) -> Option<Self>,
+    ) -> Result<Self, Self>;
+}
+
+macro_rules! impl_atomic_repr {
+    ($($int:ty => $atomic:ident, $width:literal;)*) => {
+        $(
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicRepr for $int {
+                type Atomic = core::sync::atomic::$atomic;
+
+                fn new_atomic(value: Self) -> Self::Atomic {
+                    core::sync::atomic::$atomic::new(value)
+                }
+
+                fn get_mut(atomic: &mut Self::Atomic) -> &mut Self {
+                    atomic.get_mut()
+                }
+
+                fn into_inner(atomic: Self::Atomic) -> Self {
+                    atomic.into_inner()
+                }
+
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order)
+                }
+
+                fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.swap(value, order)
+                }
+
+                fn compare_exchange(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+
+                fn compare_exchange_weak(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange_weak(current, new, success, failure)
+                }
+
+                fn fetch_update(
+                    atomic: &Self::Atomic,
+                    set_order: Ordering,
+                    fetch_order: Ordering,
+                    f: impl FnMut(Self) -> Option<Self>,
+                ) -> Result<Self, Self> {
+                    atomic.fetch_update(set_order, fetch_order, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_repr! {
+    u8 => AtomicU8, "8";
+    u16 => AtomicU16, "16";
+    u32 => AtomicU32, "32";
+    u64 => AtomicU64, "64";
+    usize => AtomicUsize, "ptr";
+    i8 => AtomicI8, "8";
+    i16 => AtomicI16, "16";
+    i32 => AtomicI32, "32";
+    i64 => AtomicI64, "64";
+    isize => AtomicIsize, "ptr";
+}
@@ -0,0 +1,317 @@
+use crate::{EnumRepr, VastEnum, VastRepr};
+use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter};
+use core::hash::Hash;
+use core::marker::PhantomData;
+use derivative::Derivative;
+
+macro_rules! vast_enum_endian {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $order:literal, $to_bytes:ident, $from_bytes:ident
+    ) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        #[derive(Derivative)]
+        #[cfg_attr(
+            feature = "zerocopy",
+            derive(
+                zerocopy::FromBytes,
+                zerocopy::IntoBytes,
+                zerocopy::KnownLayout,
+                zerocopy::Immutable,
+                zerocopy::Unaligned
+            )
+        )]
+        #[derivative(
+            Copy(bound = ""),
+            Clone(bound = ""),
+            Default(bound = ""),
+            Hash(bound = ""),
+            Eq(bound = ""),
+            PartialEq(bound = "")
+        )]
+        pub struct $name<Enum, Repr = <Enum as VastRepr>::Repr>(
+            Repr::Bytes,
+            PhantomData<Enum>,
+        )
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr;
+
+        impl<Enum, Repr> $name<Enum, Repr>
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr,
+        {
+            #[doc = concat!("Creates a [`", stringify!($name), "`] from an integer discriminant.")]
+            pub fn from_int(discriminant: Repr) -> Self {
+                $name(discriminant.$to_bytes(), PhantomData)
+            }
+
+            /// Returns the enum's integer discriminant, converted to native byte order.
+            pub fn int(self) -> Repr {
+                Repr::$from_bytes(self.0)
+            }
+
+            /// Sets the enum's integer discriminant.
+            pub fn set_int(&mut self, discriminant: Repr) {
+                self.0 = discriminant.$to_bytes();
+            }
+
+            #[doc = concat!("Returns the discriminant's bytes, in ", $order, " byte order.")]
+            pub fn bytes(self) -> Repr::Bytes {
+                self.0
+            }
+
+            #[doc = concat!("Allows casting to a [`", stringify!($name), "`] with a different enum type.")]
+            pub fn cast<EnumNew>(self) -> $name<EnumNew, Repr>
+            where
+                EnumNew: Into<Repr>,
+                Repr: EnumRepr<EnumNew>,
+            {
+                $name(self.0, PhantomData)
+            }
+
+            #[doc = concat!("Creates a [`", stringify!($name), "`] from an enum variant.")]
+            pub fn from_variant(variant: Enum) -> Self {
+                Self::from_int(variant.into())
+            }
+
+            /// Returns the wrapped enum type corresponding to the current integer discriminant, if
+            /// the integer is a valid value for that enum type.
+            pub fn variant(self) -> Option<Enum> {
+                self.native().variant()
+            }
+
+            /// Returns whether the current integer discriminant is a valid value for the wrapped
+            /// enum type.
+            pub fn is_valid(self) -> bool {
+                self.native().is_valid()
+            }
+
+            /// Transforms the wrapped enum using the provided closure.
+            pub fn map<EnumOut>(self, f: impl FnOnce(Enum) -> EnumOut) -> $name<EnumOut, Repr>
+            where
+                EnumOut: Into<Repr>,
+                Repr: EnumRepr<EnumOut>,
+            {
+                self.native().map(f).into()
+            }
+
+            /// Converts to a [`VastEnum`] in native byte order.
+            pub fn native(self) -> VastEnum<Enum, Repr> {
+                VastEnum::from_int(self.int())
+            }
+        }
+
+        impl<Enum, Repr> From<Enum> for $name<Enum, Repr>
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr,
+        {
+            fn from(enum_: Enum) -> Self {
+                $name::from_variant(enum_)
+            }
+        }
+
+        impl<Enum, Repr> From<VastEnum<Enum, Repr>> for $name<Enum, Repr>
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr,
+        {
+            fn from(enum_: VastEnum<Enum, Repr>) -> Self {
+                $name::from_int(enum_.int())
+            }
+        }
+
+        impl<Enum, Repr> From<$name<Enum, Repr>> for VastEnum<Enum, Repr>
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr,
+        {
+            fn from(enum_: $name<Enum, Repr>) -> Self {
+                enum_.native()
+            }
+        }
+
+        // SAFETY: the struct is `repr(transparent)` over `Repr::Bytes`.
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<Enum, Repr> bytemuck::Zeroable for $name<Enum, Repr>
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr,
+            Repr::Bytes: bytemuck::Zeroable,
+        {
+        }
+
+        // SAFETY: the struct is `repr(transparent)` over `Repr::Bytes`.
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<Enum, Repr> bytemuck::Pod for $name<Enum, Repr>
+        where
+            Enum: Into<Repr> + 'static,
+            Repr: EnumRepr<Enum> + EndianRepr + 'static,
+            Repr::Bytes: bytemuck::Pod,
+        {
+        }
+
+        impl<Enum, Repr> PartialOrd for $name<Enum, Repr>
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr,
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<Enum, Repr> Ord for $name<Enum, Repr>
+        where
+            Enum: Into<Repr>,
+            Repr: EnumRepr<Enum> + EndianRepr,
+        {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.int().cmp(&other.int())
+            }
+        }
+
+        impl<Enum, Repr> Debug for $name<Enum, Repr>
+        where
+            Enum: Debug + Into<Repr>,
+            Repr: Debug + EnumRepr<Enum> + EndianRepr,
+        {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                let mut tuple = f.debug_tuple(stringify!($name));
+                let int = self.int();
+                match self.variant() {
+                    Some(enum_) => {
+                        tuple.field(&format_args!("{:?}: {:?}", int, enum_));
+                    }
+                    None => {
+                        tuple.field(&int);
+                    }
+                }
+
+                tuple.finish()
+            }
+        }
+    };
+}
+
+vast_enum_endian!(
+    /// A [`VastEnum`] that stores its discriminant in little-endian byte order.
+    ///
+    /// This struct has the same in-memory representation as `[u8; size_of::<Repr>()]`, so it has an
+    /// alignment of 1 and can be used in `#[repr(C, packed)]` structs that overlay wire data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")]
+    /// # {
+    /// use core::mem::{align_of, size_of};
+    /// use vast_enum::{VastEnum, VastEnumLe};
+    ///
+    /// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    /// #[repr(u16)]
+    /// enum Kind {
+    ///     Ping = 1,
+    ///     Pong = 2,
+    /// }
+    ///
+    /// let kind = VastEnumLe::<Kind>::from_variant(Kind::Pong);
+    /// assert_eq!(kind.bytes(), [2, 0]);
+    /// assert_eq!(kind.int(), 2);
+    /// assert_eq!(align_of::<VastEnumLe<Kind>>(), 1);
+    /// assert_eq!(size_of::<VastEnumLe<Kind>>(), 2);
+    /// # }
+    /// ```
+    VastEnumLe, "little-endian", to_le_bytes, from_le_bytes
+);
+
+vast_enum_endian!(
+    /// A [`VastEnum`] that stores its discriminant in big-endian byte order.
+    ///
+    /// This struct has the same in-memory representation as `[u8; size_of::<Repr>()]`, so it has an
+    /// alignment of 1 and can be used in `#[repr(C, packed)]` structs that overlay wire data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")]
+    /// # {
+    /// use vast_enum::{VastEnum, VastEnumBe};
+    ///
+    /// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    /// #[repr(u16)]
+    /// enum Kind {
+    ///     Ping = 1,
+    ///     Pong = 2,
+    /// }
+    ///
+    /// #[repr(C, packed)]
+    /// struct Header {
+    ///     version: u8,
+    ///     kind: VastEnumBe<Kind>,
+    ///     length: [u8; 4],
+    /// }
+    ///
+    /// let header = Header {
+    ///     version: 1,
+    ///     kind: VastEnumBe::from_int(0x0102),
+    ///     length: [0; 4],
+    /// };
+    /// let kind = header.kind;
+    /// assert_eq!(kind.bytes(), [0x01, 0x02]);
+    /// assert!(!kind.is_valid());
+    /// assert_eq!(kind.map(|_| Kind::Ping).int(), 0x0102);
+    /// # }
+    /// ```
+    VastEnumBe, "big-endian", to_be_bytes, from_be_bytes
+);
+
+/// A primitive integer that can be converted to and from bytes in a fixed byte order.
+pub trait EndianRepr: Sized {
+    /// The integer's bytes, such as `[u8; 4]` for `u32`.
+    type Bytes: Copy + Default + Hash + Eq;
+
+    /// Converts the integer to little-endian bytes.
+    fn to_le_bytes(self) -> Self::Bytes;
+
+    /// Converts little-endian bytes to an integer.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Converts the integer to big-endian bytes.
+    fn to_be_bytes(self) -> Self::Bytes;
+
+    /// Converts big-endian bytes to an integer.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! impl_endian_repr {
+    ($($int:ty),*) => {
+        $(
+            impl EndianRepr for $int {
+                type Bytes = [u8; core::mem::size_of::<$int>()];
+
+                fn to_le_bytes(self) -> Self::Bytes {
+                    <$int>::to_le_bytes(self)
+                }
+
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$int>::from_le_bytes(bytes)
+                }
+
+                fn to_be_bytes(self) -> Self::Bytes {
+                    <$int>::to_be_bytes(self)
+                }
+
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$int>::from_be_bytes(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_endian_repr!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
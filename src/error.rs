@@ -0,0 +1,46 @@
+use core::fmt::{Debug, Display, Formatter};
+
+/// The error returned when an integer discriminant isn't a valid value for an enum type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct InvalidDiscriminant<Repr> {
+    value: Repr,
+    enum_name: &'static str,
+}
+
+impl<Repr> InvalidDiscriminant<Repr> {
+    /// Creates an [`InvalidDiscriminant`] for `value`, which isn't a valid discriminant of `Enum`.
+    pub fn new<Enum>(value: Repr) -> Self {
+        InvalidDiscriminant {
+            value,
+            enum_name: core::any::type_name::<Enum>(),
+        }
+    }
+
+    /// Returns the rejected integer discriminant.
+    pub fn value(&self) -> &Repr {
+        &self.value
+    }
+
+    /// Consumes the error, returning the rejected integer discriminant.
+    pub fn into_value(self) -> Repr {
+        self.value
+    }
+
+    /// Returns the name of the enum type, as given by [`core::any::type_name`].
+    pub fn enum_name(&self) -> &'static str {
+        self.enum_name
+    }
+}
+
+impl<Repr: Display> Display for InvalidDiscriminant<Repr> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid discriminant {} for enum `{}`",
+            self.value, self.enum_name
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Repr: Debug + Display> std::error::Error for InvalidDiscriminant<Repr> {}
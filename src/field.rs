@@ -0,0 +1,214 @@
+use crate::{VastEnum, VastRepr};
+use core::fmt::{Debug, Formatter};
+use core::hash::Hash;
+use core::marker::PhantomData;
+use derivative::Derivative;
+
+/// A [`VastEnum`] packed into `WIDTH` bits of a larger `Container` integer, starting `OFFSET` bits
+/// from the least significant bit.
+///
+/// Reading the field extracts its bits as the enum's discriminant, and writing it replaces only
+/// those bits, so reserved bits and other fields in the container are kept intact. Bits of a
+/// written discriminant that don't fit in `WIDTH` are discarded. Using a field that doesn't fit in
+/// `Container` or `Enum`'s repr is a compile-time error.
+///
+/// This struct has the same in-memory representation as `Container`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{VastEnum, VastField};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum Priority {
+///     Low = 0,
+///     Normal = 1,
+///     High = 2,
+/// }
+///
+/// // Bits 4 to 6 of the header byte.
+/// type PriorityField = VastField<Priority, u8, 4, 3>;
+///
+/// let mut header = PriorityField::from_container(0b1_010_1001);
+/// assert_eq!(header.variant(), Some(Priority::High));
+///
+/// header.set_variant(Priority::Normal);
+/// assert_eq!(header.container(), 0b1_001_1001);
+///
+/// header.set_int(0b111);
+/// assert!(!header.is_valid());
+/// assert_eq!(header.get().int(), 0b111);
+/// assert_eq!(header.container(), 0b1_111_1001);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use vast_enum::{VastEnum, VastField};
+/// # #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// # #[repr(u8)]
+/// # enum Priority {
+/// #     Low = 0,
+/// # }
+/// // Bits 6 to 8 don't fit in a `u8`.
+/// let field = VastField::<Priority, u8, 6, 3>::from_container(0);
+/// field.variant();
+/// ```
+#[repr(transparent)]
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = ""),
+    Clone(bound = ""),
+    Default(bound = ""),
+    Hash(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct VastField<Enum, Container, const OFFSET: u32, const WIDTH: u32>
+where
+    Enum: VastRepr,
+    Container: FieldContainer<Enum::Repr>,
+{
+    container: Container,
+    phantom: PhantomData<Enum>,
+}
+
+impl<Enum, Container, const OFFSET: u32, const WIDTH: u32> VastField<Enum, Container, OFFSET, WIDTH>
+where
+    Enum: VastRepr,
+    Container: FieldContainer<Enum::Repr>,
+{
+    const FITS: () = assert!(
+        WIDTH > 0
+            && WIDTH <= Container::BITS
+            && OFFSET <= Container::BITS - WIDTH
+            && WIDTH as usize <= core::mem::size_of::<Enum::Repr>() * 8,
+        "the field doesn't fit in the container or the enum's repr"
+    );
+
+    /// Creates a [`VastField`] from the whole container integer.
+    pub fn from_container(container: Container) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS;
+        VastField {
+            container,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the whole container integer, including the bits outside the field.
+    pub fn container(self) -> Container {
+        self.container
+    }
+
+    /// Returns a mutable reference to the whole container integer.
+    pub fn container_mut(&mut self) -> &mut Container {
+        &mut self.container
+    }
+
+    /// Returns the field's bits as the enum's integer discriminant.
+    pub fn int(self) -> Enum::Repr {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS;
+        self.container.extract(OFFSET, WIDTH)
+    }
+
+    /// Replaces the field's bits with `discriminant`, discarding any bits that don't fit.
+    pub fn set_int(&mut self, discriminant: Enum::Repr) {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS;
+        self.container = self.container.insert(OFFSET, WIDTH, discriminant);
+    }
+
+    /// Returns the field as a [`VastEnum`].
+    pub fn get(self) -> VastEnum<Enum> {
+        VastEnum::from_int(self.int())
+    }
+
+    /// Replaces the field's bits with the discriminant of `value`, discarding any bits that don't
+    /// fit.
+    pub fn set(&mut self, value: VastEnum<Enum>) {
+        self.set_int(value.int())
+    }
+
+    /// Returns the enum variant stored in the field, if its bits are a valid discriminant.
+    pub fn variant(self) -> Option<Enum> {
+        self.get().variant()
+    }
+
+    /// Returns whether the field's bits are a valid discriminant for the enum type.
+    pub fn is_valid(self) -> bool {
+        self.get().is_valid()
+    }
+
+    /// Stores an enum variant in the field.
+    pub fn set_variant(&mut self, variant: Enum) {
+        self.set(VastEnum::from_variant(variant))
+    }
+}
+
+impl<Enum, Container, const OFFSET: u32, const WIDTH: u32> Debug
+    for VastField<Enum, Container, OFFSET, WIDTH>
+where
+    Enum: Debug + VastRepr,
+    Enum::Repr: Debug,
+    Container: FieldContainer<Enum::Repr>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut tuple = f.debug_tuple("VastField");
+        let int = self.int();
+        match self.variant() {
+            Some(enum_) => {
+                tuple.field(&format_args!("{:?}: {:?}", int, enum_));
+            }
+            None => {
+                tuple.field(&int);
+            }
+        }
+
+        tuple.finish()
+    }
+}
+
+/// An unsigned integer that can hold a [`VastField`] whose discriminants are of type `Repr`.
+///
+/// This is implemented for every pair of unsigned primitive integers.
+pub trait FieldContainer<Repr>: Copy + Default + Hash + Eq {
+    /// The number of bits in the container.
+    const BITS: u32;
+
+    /// Returns the `width` bits starting at `offset`.
+    fn extract(self, offset: u32, width: u32) -> Repr;
+
+    /// Replaces the `width` bits starting at `offset` with the low bits of `value`.
+    fn insert(self, offset: u32, width: u32, value: Repr) -> Self;
+}
+
+macro_rules! impl_field_container {
+    ($($container:ty),*) => {
+        $(
+            impl_field_container!(@reprs $container; u8, u16, u32, u64, u128);
+        )*
+    };
+    (@reprs $container:ty; $($repr:ty),*) => {
+        $(
+            impl FieldContainer<$repr> for $container {
+                const BITS: u32 = <$container>::BITS;
+
+                fn extract(self, offset: u32, width: u32) -> $repr {
+                    let mask = <$container>::MAX >> (Self::BITS - width);
+                    (self >> offset & mask) as $repr
+                }
+
+                fn insert(self, offset: u32, width: u32, value: $repr) -> Self {
+                    let mask = <$container>::MAX >> (Self::BITS - width);
+                    self & !(mask << offset) | (value as $container & mask) << offset
+                }
+            }
+        )*
+    };
+}
+
+impl_field_container!(u8, u16, u32, u64, u128);
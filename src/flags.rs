@@ -0,0 +1,360 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Variants, VastRepr};
+use core::fmt::{Debug, Formatter};
+use core::hash::Hash;
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+use derivative::Derivative;
+
+/// A set of bit flags that keeps bits which don't correspond to any known flag.
+///
+/// `Flags` is a fieldless enum whose discriminants are bit masks. Bits that aren't covered by any
+/// of its variants are kept as-is, so a value read from a peer that knows more flags is written
+/// back unchanged.
+///
+/// This struct has the same in-memory representation as `Repr`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{VastEnum, VastFlags};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum Permission {
+///     Read = 0b001,
+///     Write = 0b010,
+///     Execute = 0b100,
+/// }
+///
+/// let mut flags = VastFlags::<Permission>::from_bits(0b1000_0101);
+/// assert!(flags.contains(Permission::Read));
+/// assert!(!flags.contains(Permission::Write));
+/// assert_eq!(flags.unknown_bits(), 0b1000_0000);
+///
+/// flags.insert(Permission::Write);
+/// flags.remove(Permission::Execute);
+/// assert_eq!(flags.known().bits(), 0b011);
+/// assert_eq!(flags.bits(), 0b1000_0011);
+///
+/// let set: Vec<_> = flags.iter().collect();
+/// assert_eq!(set, [Permission::Read, Permission::Write]);
+/// # }
+/// ```
+#[repr(transparent)]
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = ""),
+    Clone(bound = ""),
+    Default(bound = ""),
+    Hash(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
+pub struct VastFlags<Flags, Repr = <Flags as VastRepr>::Repr>(
+    Repr,
+    #[cfg_attr(feature = "serde", serde(skip))] PhantomData<Flags>,
+)
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr;
+
+impl<Flags, Repr> VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    /// Creates an empty [`VastFlags`].
+    pub fn empty() -> Self {
+        Self::from_bits(Repr::default())
+    }
+
+    /// Creates a [`VastFlags`] with every known flag set.
+    pub fn all_known() -> Self {
+        Self::from_bits(Self::known_mask())
+    }
+
+    /// Creates a [`VastFlags`] from raw bits, including any unknown ones.
+    pub fn from_bits(bits: Repr) -> Self {
+        VastFlags(bits, PhantomData)
+    }
+
+    /// Returns the raw bits, including any unknown ones.
+    pub fn bits(self) -> Repr {
+        self.0
+    }
+
+    /// Returns a mutable reference to the raw bits.
+    pub fn bits_mut(&mut self) -> &mut Repr {
+        &mut self.0
+    }
+
+    /// Returns whether no bits are set.
+    pub fn is_empty(self) -> bool {
+        self.0 == Repr::default()
+    }
+
+    /// Returns whether all bits of `flag` are set.
+    pub fn contains(self, flag: Flags) -> bool {
+        let flag = flag.into();
+        self.0 & flag == flag
+    }
+
+    /// Sets the bits of `flag`.
+    pub fn insert(&mut self, flag: Flags) {
+        self.0 = self.0 | flag.into();
+    }
+
+    /// Clears the bits of `flag`.
+    pub fn remove(&mut self, flag: Flags) {
+        self.0 = self.0 & !flag.into();
+    }
+
+    /// Returns only the bits that belong to a known flag.
+    pub fn known(self) -> Self {
+        Self::from_bits(self.0 & Self::known_mask())
+    }
+
+    /// Returns the bits that don't belong to any known flag.
+    pub fn unknown_bits(self) -> Repr {
+        self.0 & !Self::known_mask()
+    }
+
+    /// Returns an iterator over the known flags that are set, in declaration order.
+    ///
+    /// Flags without any bits are skipped.
+    pub fn iter(self) -> FlagsIter<Flags, Repr> {
+        FlagsIter {
+            flags: self,
+            remaining: Flags::VARIANTS.iter(),
+        }
+    }
+
+    fn known_mask() -> Repr {
+        Flags::VARIANTS
+            .iter()
+            .fold(Repr::default(), |mask, &flag| mask | flag.into())
+    }
+}
+
+impl<Flags, Repr> From<Flags> for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    fn from(flag: Flags) -> Self {
+        VastFlags::from_bits(flag.into())
+    }
+}
+
+impl<Flags, Repr> FromIterator<Flags> for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    fn from_iter<I: IntoIterator<Item = Flags>>(iter: I) -> Self {
+        let mut flags = VastFlags::empty();
+        for flag in iter {
+            flags.insert(flag);
+        }
+
+        flags
+    }
+}
+
+impl<Flags, Repr> IntoIterator for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    type Item = Flags;
+    type IntoIter = FlagsIter<Flags, Repr>;
+
+    fn into_iter(self) -> FlagsIter<Flags, Repr> {
+        self.iter()
+    }
+}
+
+impl<Flags, Repr> BitOr for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        VastFlags::from_bits(self.0 | rhs.0)
+    }
+}
+
+impl<Flags, Repr> BitOr<Flags> for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Flags) -> Self {
+        VastFlags::from_bits(self.0 | rhs.into())
+    }
+}
+
+impl<Flags, Repr> BitOrAssign for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl<Flags, Repr> BitAnd for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        VastFlags::from_bits(self.0 & rhs.0)
+    }
+}
+
+impl<Flags, Repr> BitAndAssign for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+/// Flips every bit, including unknown ones.
+impl<Flags, Repr> Not for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    type Output = Self;
+
+    fn not(self) -> Self {
+        VastFlags::from_bits(!self.0)
+    }
+}
+
+impl<Flags, Repr> Debug for VastFlags<Flags, Repr>
+where
+    Flags: Debug + Variants + Copy + Into<Repr>,
+    Repr: Debug + FlagsRepr,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        struct Names<Flags, Repr>(VastFlags<Flags, Repr>)
+        where
+            Flags: Variants + Copy + Into<Repr>,
+            Repr: FlagsRepr;
+
+        impl<Flags, Repr> Debug for Names<Flags, Repr>
+        where
+            Flags: Debug + Variants + Copy + Into<Repr>,
+            Repr: Debug + FlagsRepr,
+        {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{:?}:", self.0.bits())?;
+                let mut separator = " ";
+                for flag in self.0.iter() {
+                    write!(f, "{}{:?}", separator, flag)?;
+                    separator = " | ";
+                }
+
+                let unknown = self.0.unknown_bits();
+                if unknown != Repr::default() {
+                    write!(f, "{}{:?}", separator, unknown)?;
+                }
+
+                Ok(())
+            }
+        }
+
+        f.debug_tuple("VastFlags").field(&Names(*self)).finish()
+    }
+}
+
+// SAFETY: `VastFlags` is `repr(transparent)` over `Repr`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<Flags, Repr> bytemuck::Zeroable for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr + bytemuck::Zeroable,
+{
+}
+
+// SAFETY: `VastFlags` is `repr(transparent)` over `Repr`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<Flags, Repr> bytemuck::Pod for VastFlags<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr + bytemuck::Pod,
+{
+}
+
+/// An iterator over the known flags set in a [`VastFlags`].
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct FlagsIter<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    flags: VastFlags<Flags, Repr>,
+    remaining: core::slice::Iter<'static, Flags>,
+}
+
+impl<Flags, Repr> Iterator for FlagsIter<Flags, Repr>
+where
+    Flags: Variants + Copy + Into<Repr>,
+    Repr: FlagsRepr,
+{
+    type Item = Flags;
+
+    fn next(&mut self) -> Option<Flags> {
+        let flags = self.flags;
+        self.remaining
+            .by_ref()
+            .copied()
+            .find(|&flag| flag.into() != Repr::default() && flags.contains(flag))
+    }
+}
+
+/// A wrapper for traits that valid flag reprs implement.
+pub trait FlagsRepr:
+    Copy + Default + Hash + Eq + BitAnd<Output = Self> + BitOr<Output = Self> + Not<Output = Self>
+{
+}
+
+impl<Repr> FlagsRepr for Repr where
+    Repr: Copy
+        + Default
+        + Hash
+        + Eq
+        + BitAnd<Output = Self>
+        + BitOr<Output = Self>
+        + Not<Output = Self>
+{
+}
@@ -1,16 +1,18 @@
 //! A wrapper for fieldless enums that allows representing invalid enum discriminants.
 //!
 //! The wrapped enum must implement `Into<Repr>`, where `Repr` is a primitive integer, and `Repr`
-//! must implement `TryInto<Enum>`. These impls can be easily derived using the [num_enum][1] crate,
-//! as shown in the example below.
+//! must implement `TryInto<Enum>`. These impls can be generated with `#[derive(VastEnum)]`, as
+//! shown in the example below, or with the [num_enum][1] crate's `IntoPrimitive` and
+//! `TryFromPrimitive` derives.
 //!
 //! # Example
 //!
 //! ```
-//! use num_enum::{IntoPrimitive, TryFromPrimitive};
+//! # #[cfg(feature = "derive")]
+//! # {
 //! use vast_enum::VastEnum;
 //!
-//! #[derive(Debug, Copy, Clone, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+//! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
 //! #[repr(u8)]
 //! enum Color {
 //!     Red = 0,
@@ -29,40 +31,278 @@
 //! *enum_.int_mut() = 5;
 //! assert!(!enum_.is_valid());
 //! assert_eq!(enum_.variant(), None);
+//! # }
 //! ```
 //!
+//! # Deriving
+//!
+//! With the `derive` feature (enabled by default), `#[derive(VastEnum)]` reads the enum's
+//! `#[repr(..)]` and implements both conversions through it. It also implements [`VastRepr`], so
+//! the repr doesn't need to be repeated at use sites, and adds a `Vast{Enum}` type alias:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use vast_enum::VastEnum;
+//!
+//! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+//! #[repr(u16)]
+//! pub enum Opcode {
+//!     Nop = 0x00,
+//!     Load = 0x10,
+//!     Store = 0x11,
+//! }
+//!
+//! let op: VastOpcode = VastEnum::from_int(0x11);
+//! assert_eq!(op.variant(), Some(Opcode::Store));
+//!
+//! let op = VastEnum::<Opcode>::from_int(0x10);
+//! assert_eq!(op.variant(), Some(Opcode::Load));
+//! # }
+//! ```
+//!
+//! Variants can also be declared with character literals instead of discriminants, for text
+//! protocols whose tags are single characters or fixed-size codes. The repr is then `char`,
+//! [`AsciiByte`] for byte literals, or [`ByteTag`] for byte-string literals such as `b"fmt "`, and
+//! no `#[repr(..)]` is needed:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use vast_enum::VastEnum;
+//!
+//! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+//! enum MsgType {
+//!     #[vast_enum(value = 'A')]
+//!     Logon,
+//!     #[vast_enum(value = 'D')]
+//!     NewOrderSingle,
+//!     #[vast_enum(value = '8')]
+//!     ExecutionReport,
+//! }
+//!
+//! let msg_type = VastMsgType::from_int('D');
+//! assert_eq!(msg_type.variant(), Some(MsgType::NewOrderSingle));
+//! assert_eq!(format!("{:?}", VastMsgType::from_int('Z')), "VastEnum('Z')");
+//! assert_eq!(VastMsgType::EXECUTION_REPORT.to_string(), "8");
+//! # }
+//! ```
+//!
+//! Discriminants must fit in the declared repr:
+//!
+//! ```compile_fail
+//! use vast_enum::VastEnum;
+//!
+//! #[derive(VastEnum)]
+//! #[repr(u8)]
+//! enum TooBig {
+//!     Small = 1,
+//!     Large = 256,
+//! }
+//! ```
+//!
+//! # Casting from bytes
+//!
+//! With the `bytemuck` or `zerocopy` feature, structs with [`VastEnum`] and [`VastEnumBe`] fields
+//! can be cast directly from a byte buffer, without any `unsafe` code. Every bit pattern is a valid
+//! value, so unknown discriminants are read as-is:
+//!
+//! ```
+//! # #[cfg(all(feature = "derive", feature = "bytemuck", feature = "zerocopy"))]
+//! # {
+//! use vast_enum::{VastEnum, VastEnumBe};
+//! use zerocopy::FromBytes;
+//!
+//! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+//! #[repr(u8)]
+//! enum Kind {
+//!     Request = 1,
+//!     Response = 2,
+//! }
+//!
+//! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+//! #[repr(u16)]
+//! enum Status {
+//!     Ok = 200,
+//!     NotFound = 404,
+//! }
+//!
+//! #[derive(
+//!     Copy,
+//!     Clone,
+//!     bytemuck::Zeroable,
+//!     bytemuck::Pod,
+//!     zerocopy::FromBytes,
+//!     zerocopy::IntoBytes,
+//!     zerocopy::KnownLayout,
+//!     zerocopy::Immutable,
+//! )]
+//! #[repr(C, packed)]
+//! struct Header {
+//!     kind: VastKind,
+//!     status: VastEnumBe<Status>,
+//! }
+//!
+//! let bytes = [2, 0x01, 0x94, 7, 0x01, 0xF4];
+//!
+//! let header: &Header = bytemuck::from_bytes(&bytes[..3]);
+//! assert_eq!(header.kind.variant(), Some(Kind::Response));
+//! assert_eq!(header.status.variant(), Some(Status::NotFound));
+//!
+//! let header = Header::ref_from_bytes(&bytes[3..]).unwrap();
+//! assert_eq!(header.kind.variant(), None);
+//! assert_eq!(header.kind.int(), 7);
+//! assert_eq!(header.status.variant(), None);
+//! assert_eq!(header.status.int(), 500);
+//! # }
+//! ```
+//!
+//! # Cargo features
+//!
+//! - `derive` (default): enables `#[derive(VastEnum)]`.
+//! - `alloc`: adds [`VastStrEnum`], for enums whose values are strings, and [`VastUnion`], for
+//!   enums with data whose unknown tags keep their raw payload.
+//! - `std`: implements `std::error::Error` for the error types, such as [`InvalidDiscriminant`].
+//!   Implies `alloc`.
+//! - `serde`: implements `Serialize` and `Deserialize`, and adds the [`serde`] module with
+//!   alternative representations.
+//! - `bytemuck`: implements `Zeroable` and `Pod` when `Repr` does.
+//! - `zerocopy`: implements `FromBytes`, `IntoBytes`, `KnownLayout` and `Immutable` when `Repr`
+//!   does. The endian-fixed wrappers also implement `Unaligned`.
+//! - `arbitrary-int`: implements [`NarrowRepr`] for the odd-width integers of the
+//!   [arbitrary-int][2] crate, such as `u3`, so they can be used as reprs like the `NonZero*`
+//!   integers.
+//!
 //! [1]: https://crates.io/crates/num_enum
+//! [2]: https://crates.io/crates/arbitrary-int
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use ::serde::{Deserialize, Serialize};
 
 use core::borrow::Borrow;
 use core::convert::TryInto;
-use core::fmt::{Debug, Formatter};
+use core::fmt::{Debug, Display, Formatter};
 use core::hash::Hash;
 use core::marker::PhantomData;
 use derivative::Derivative;
 
+pub use ascii::{AsciiByte, ParseAsciiError};
+pub use atomic::{AtomicRepr, AtomicVastEnum};
+pub use endian::{EndianRepr, VastEnumBe, VastEnumLe};
+pub use error::InvalidDiscriminant;
+pub use field::{FieldContainer, VastField};
+pub use flags::{FlagsIter, FlagsRepr, VastFlags};
+pub use narrow::{NarrowConst, NarrowRepr};
+pub use slice::{InvalidIndices, VastSlice};
+#[cfg(feature = "alloc")]
+pub use str_enum::VastStrEnum;
+pub use table::{DiscriminantSet, TableRepr, ValidityTable};
+pub use tag::{ByteTag, FourCc};
+#[cfg(feature = "alloc")]
+pub use union::{InvalidPayload, Payload, TaggedUnion, VastUnion};
+pub use variant::VastVariant;
+#[cfg(feature = "derive")]
+pub use vast_enum_derive::VastEnum;
+#[cfg(all(feature = "derive", feature = "alloc"))]
+pub use vast_enum_derive::VastUnion;
+pub use volatile::VolatileVastEnum;
+
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub use alloc::vec::Vec as __Vec;
+
+mod ascii;
+mod atomic;
+mod endian;
+mod error;
+mod field;
+mod flags;
+mod narrow;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod slice;
+#[cfg(feature = "alloc")]
+mod str_enum;
+mod table;
+mod tag;
+#[cfg(feature = "alloc")]
+mod union;
+mod variant;
+mod volatile;
+
 /// A wrapper for fieldless enums that allows representing invalid enum discriminants.
 ///
 /// This struct has the same in-memory representation as `Repr`, which represents the enum's integer
-/// discriminant.
+/// discriminant. `Repr` can be omitted for enums that implement [`VastRepr`].
+///
+/// `PartialEq` and `Eq` are derived, so constants of this type can be used as patterns. Because of
+/// that, comparing values requires the enum type to implement them as well.
+///
+/// # Constants
+///
+/// [`from_int`](VastEnum::from_int) is a `const fn`, so values can be built in `const` and `static`
+/// items. `#[derive(VastEnum)]` also generates a `Vast{Enum}Consts` trait with a constant for each
+/// variant, named in `SCREAMING_SNAKE_CASE`, which is implemented for the `Vast{Enum}` type alias:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::VastEnum;
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop = 0,
+///     LoadImmediate = 1,
+///     Halt = 0xFF,
+/// }
+///
+/// static PROGRAM: [VastOpcode; 3] = [
+///     VastOpcode::LOAD_IMMEDIATE,
+///     VastOpcode::from_int(0x42),
+///     VastOpcode::HALT,
+/// ];
+///
+/// let names: Vec<_> = PROGRAM
+///     .iter()
+///     .map(|&op| match op {
+///         VastOpcode::NOP => "nop",
+///         VastOpcode::LOAD_IMMEDIATE => "li",
+///         VastOpcode::HALT => "halt",
+///         _ => "?",
+///     })
+///     .collect();
+/// assert_eq!(names, ["li", "?", "halt"]);
+/// # }
+/// ```
 #[repr(transparent)]
-#[derive(Derivative)]
+#[derive(Derivative, Eq, PartialEq)]
 #[derivative(
     Copy(bound = ""),
     Clone(bound = ""),
-    Default(bound = ""),
+    Default(bound = "Repr: Default"),
     Hash(bound = ""),
-    Eq(bound = ""),
-    PartialEq(bound = ""),
-    Ord(bound = ""),
-    PartialOrd(bound = "")
+    Ord(bound = "Enum: Eq"),
+    PartialOrd(bound = "Enum: PartialEq")
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
 )]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VastEnum<Enum, Repr>(
+pub struct VastEnum<Enum, Repr = <Enum as VastRepr>::Repr>(
     Repr,
     #[cfg_attr(feature = "serde", serde(skip))] PhantomData<Enum>,
 )
@@ -76,12 +316,12 @@ where
     Repr: EnumRepr<Enum>,
 {
     /// Creates a [`VastEnum`] from an integer discriminant.
-    pub fn from_int(discriminant: Repr) -> Self {
+    pub const fn from_int(discriminant: Repr) -> Self {
         VastEnum(discriminant, PhantomData)
     }
 
     /// Returns the enum's integer discriminant.
-    pub fn int(self) -> Repr {
+    pub const fn int(self) -> Repr {
         self.0
     }
 
@@ -90,11 +330,85 @@ where
         &mut self.0
     }
 
+    /// Views a reference to an integer discriminant as a [`VastEnum`].
+    pub fn from_int_ref(discriminant: &Repr) -> &Self {
+        // SAFETY: `VastEnum` is `repr(transparent)` over `Repr`, and any `Repr` is a valid
+        // `VastEnum`.
+        unsafe { &*(discriminant as *const Repr as *const Self) }
+    }
+
+    /// Views a mutable reference to an integer discriminant as a [`VastEnum`].
+    pub fn from_int_mut(discriminant: &mut Repr) -> &mut Self {
+        // SAFETY: `VastEnum` is `repr(transparent)` over `Repr`, and any `Repr` is a valid
+        // `VastEnum`.
+        unsafe { &mut *(discriminant as *mut Repr as *mut Self) }
+    }
+
+    /// Views a slice of integer discriminants as a slice of [`VastEnum`]s, without copying.
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")]
+    /// # {
+    /// use vast_enum::VastEnum;
+    ///
+    /// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    /// #[repr(u16)]
+    /// enum Opcode {
+    ///     Nop = 0x00,
+    ///     Load = 0x10,
+    /// }
+    ///
+    /// let mut buffer = [0x10, 0x00, 0x99];
+    ///
+    /// let ops = VastOpcode::from_int_slice(&buffer);
+    /// assert_eq!(ops[0].variant(), Some(Opcode::Load));
+    /// assert_eq!(ops[2].variant(), None);
+    ///
+    /// let ops = VastOpcode::from_int_slice_mut(&mut buffer);
+    /// ops[2] = VastEnum::from_variant(Opcode::Nop);
+    /// assert_eq!(VastOpcode::as_int_slice(ops), [0x10, 0x00, 0x00]);
+    /// assert_eq!(buffer, [0x10, 0x00, 0x00]);
+    /// # }
+    /// ```
+    pub fn from_int_slice(discriminants: &[Repr]) -> &[Self] {
+        // SAFETY: `VastEnum` is `repr(transparent)` over `Repr`, so the slices have the same
+        // layout, and any `Repr` is a valid `VastEnum`.
+        unsafe {
+            core::slice::from_raw_parts(discriminants.as_ptr() as *const Self, discriminants.len())
+        }
+    }
+
+    /// Views a mutable slice of integer discriminants as a slice of [`VastEnum`]s, without copying.
+    pub fn from_int_slice_mut(discriminants: &mut [Repr]) -> &mut [Self] {
+        // SAFETY: `VastEnum` is `repr(transparent)` over `Repr`, so the slices have the same
+        // layout, and any `Repr` is a valid `VastEnum`.
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                discriminants.as_mut_ptr() as *mut Self,
+                discriminants.len(),
+            )
+        }
+    }
+
+    /// Views a slice of [`VastEnum`]s as a slice of integer discriminants, without copying.
+    pub fn as_int_slice(enums: &[Self]) -> &[Repr] {
+        // SAFETY: `VastEnum` is `repr(transparent)` over `Repr`, so the slices have the same
+        // layout.
+        unsafe { core::slice::from_raw_parts(enums.as_ptr() as *const Repr, enums.len()) }
+    }
+
+    /// Views a mutable slice of [`VastEnum`]s as a slice of integer discriminants, without copying.
+    pub fn as_int_slice_mut(enums: &mut [Self]) -> &mut [Repr] {
+        // SAFETY: `VastEnum` is `repr(transparent)` over `Repr`, so the slices have the same
+        // layout, and any `Repr` is a valid `VastEnum`.
+        unsafe { core::slice::from_raw_parts_mut(enums.as_mut_ptr() as *mut Repr, enums.len()) }
+    }
+
     /// Allows casting to a [`VastEnum`] with a different enum type.
     ///
     /// Equivalent to
     ///
-    /// ```rust
+    /// ```ignore
     /// VastEnum::from_int(vast_enum.int())
     /// ```
     pub fn cast<EnumNew>(self) -> VastEnum<EnumNew, Repr>
@@ -116,7 +430,43 @@ where
         self.0.try_into().ok()
     }
 
+    /// Returns the wrapped enum type corresponding to the current integer discriminant, or an error
+    /// carrying the discriminant if it's not a valid value for that enum type.
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")]
+    /// # {
+    /// use std::convert::TryInto;
+    /// use vast_enum::VastEnum;
+    ///
+    /// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    /// #[repr(u8)]
+    /// enum Color {
+    ///     Red = 0,
+    ///     Yellow = 1,
+    ///     Green = 2,
+    /// }
+    ///
+    /// assert_eq!(VastColor::from_int(2).try_variant(), Ok(Color::Green));
+    ///
+    /// let error = VastColor::from_int(5).try_variant().unwrap_err();
+    /// assert_eq!(*error.value(), 5);
+    /// assert!(error.to_string().starts_with("invalid discriminant 5 for enum"));
+    ///
+    /// let color: Result<Color, _> = VastColor::from_int(1).try_into();
+    /// assert_eq!(color, Ok(Color::Yellow));
+    /// # }
+    /// ```
+    pub fn try_variant(self) -> Result<Enum, InvalidDiscriminant<Repr>> {
+        self.0
+            .try_into()
+            .map_err(|_| InvalidDiscriminant::new::<Enum>(self.0))
+    }
+
     /// Returns whether the current integer discriminant is a valid value for the wrapped enum type.
+    ///
+    /// For enums that derive `VastEnum`, this is a single lookup in their [`ValidityTable`], since
+    /// the derived `TryFrom` checks the table before matching.
     pub fn is_valid(self) -> bool {
         self.variant().is_some()
     }
@@ -132,6 +482,122 @@ where
             None => self.cast(),
         }
     }
+
+    /// Transforms the wrapped enum into another [`VastEnum`] using the provided closure. Unknown
+    /// discriminants are kept as-is.
+    pub fn and_then<EnumOut>(
+        self,
+        f: impl FnOnce(Enum) -> VastEnum<EnumOut, Repr>,
+    ) -> VastEnum<EnumOut, Repr>
+    where
+        EnumOut: Into<Repr>,
+        Repr: EnumRepr<EnumOut>,
+    {
+        match self.variant() {
+            Some(enum_) => f(enum_),
+            None => self.cast(),
+        }
+    }
+
+    /// Applies `f` to the wrapped enum, or returns `default` if the discriminant isn't valid.
+    pub fn map_or<T>(self, default: T, f: impl FnOnce(Enum) -> T) -> T {
+        match self.variant() {
+            Some(enum_) => f(enum_),
+            None => default,
+        }
+    }
+
+    /// Applies `f` to the wrapped enum, or `default` to the integer discriminant if it isn't valid.
+    pub fn map_or_else<T>(self, default: impl FnOnce(Repr) -> T, f: impl FnOnce(Enum) -> T) -> T {
+        match self.variant() {
+            Some(enum_) => f(enum_),
+            None => default(self.0),
+        }
+    }
+
+    /// Returns the wrapped enum, or `default` if the discriminant isn't valid.
+    pub fn unwrap_or(self, default: Enum) -> Enum {
+        self.variant().unwrap_or(default)
+    }
+
+    /// Returns the wrapped enum, or computes one from the integer discriminant if it isn't valid.
+    pub fn unwrap_or_else(self, f: impl FnOnce(Repr) -> Enum) -> Enum {
+        match self.variant() {
+            Some(enum_) => enum_,
+            None => f(self.0),
+        }
+    }
+
+    /// Returns the wrapped enum if the discriminant is valid and `predicate` returns `true` for it.
+    pub fn filter(self, predicate: impl FnOnce(&Enum) -> bool) -> Option<Enum> {
+        self.variant().filter(predicate)
+    }
+
+    /// Returns the wrapped enum, or `err` if the discriminant isn't valid.
+    pub fn ok_or<E>(self, err: E) -> Result<Enum, E> {
+        self.variant().ok_or(err)
+    }
+
+    /// Returns the wrapped enum.
+    ///
+    /// # Panics
+    ///
+    /// Panics with `msg` if the discriminant isn't valid.
+    #[track_caller]
+    pub fn expect_variant(self, msg: &str) -> Enum {
+        match self.variant() {
+            Some(enum_) => enum_,
+            None => panic!("{}", msg),
+        }
+    }
+
+    /// Returns the integer discriminant if it isn't a valid value for the wrapped enum type.
+    pub fn unknown(self) -> Option<Repr> {
+        match self.variant() {
+            Some(_) => None,
+            None => Some(self.0),
+        }
+    }
+
+    /// Casts to a [`VastEnum`] with a different enum type, if the discriminant is a valid value for
+    /// that enum type.
+    ///
+    /// Unlike [`cast`](VastEnum::cast), this never produces a value that's invalid for the new
+    /// enum type.
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")]
+    /// # {
+    /// use vast_enum::VastEnum;
+    ///
+    /// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    /// #[repr(u8)]
+    /// enum Request {
+    ///     Get = 1,
+    ///     Put = 2,
+    /// }
+    ///
+    /// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    /// #[repr(u8)]
+    /// enum Response {
+    ///     Ok = 1,
+    /// }
+    ///
+    /// let get = VastRequest::from_variant(Request::Get);
+    /// assert_eq!(get.try_cast::<Response>().unwrap().variant(), Some(Response::Ok));
+    ///
+    /// let put = VastRequest::from_variant(Request::Put);
+    /// assert_eq!(*put.try_cast::<Response>().unwrap_err().value(), 2);
+    /// # }
+    /// ```
+    pub fn try_cast<EnumNew>(self) -> Result<VastEnum<EnumNew, Repr>, InvalidDiscriminant<Repr>>
+    where
+        EnumNew: Into<Repr>,
+        Repr: EnumRepr<EnumNew>,
+    {
+        let cast = self.cast::<EnumNew>();
+        cast.try_variant().map(|_| cast)
+    }
 }
 
 impl<Enum, Repr> From<Enum> for VastEnum<Enum, Repr>
@@ -194,8 +660,84 @@ where
     }
 }
 
+/// Formats the discriminant, such as the character of a `char` repr.
+impl<Enum, Repr> Display for VastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: Display + EnumRepr<Enum>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+// SAFETY: `VastEnum` is `repr(transparent)` over `Repr`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<Enum, Repr> bytemuck::Zeroable for VastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + bytemuck::Zeroable,
+{
+}
+
+// SAFETY: `VastEnum` is `repr(transparent)` over `Repr`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<Enum, Repr> bytemuck::Pod for VastEnum<Enum, Repr>
+where
+    Enum: Into<Repr> + 'static,
+    Repr: EnumRepr<Enum> + bytemuck::Pod,
+{
+}
+
+/// Associates an enum with the integer type of its discriminant.
+///
+/// This lets `VastEnum<Enum>` be written instead of `VastEnum<Enum, Repr>`. It's implemented by
+/// `#[derive(VastEnum)]`, and can also be implemented by hand:
+///
+/// ```
+/// use num_enum::{IntoPrimitive, TryFromPrimitive};
+/// use vast_enum::{VastEnum, VastRepr};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+/// #[repr(u8)]
+/// enum Color {
+///     Red = 0,
+///     Yellow = 1,
+///     Green = 2,
+/// }
+///
+/// impl VastRepr for Color {
+///     type Repr = u8;
+/// }
+///
+/// let enum_ = VastEnum::<Color>::from_int(1);
+/// assert_eq!(enum_.variant(), Some(Color::Yellow));
+/// ```
+pub trait VastRepr: Into<Self::Repr> {
+    /// The enum's integer discriminant type.
+    type Repr: EnumRepr<Self>;
+}
+
+/// Lists every variant of a fieldless enum.
+///
+/// This is implemented by `#[derive(VastEnum)]`. It's needed by [`VastFlags`] to tell known bits
+/// from unknown ones, by [`VastStrEnum`], and by the [`serde::named`] representation.
+pub trait Variants: Sized + 'static {
+    /// Every variant, in declaration order.
+    const VARIANTS: &'static [Self];
+
+    /// The name of every variant, in the same order as [`VARIANTS`](Variants::VARIANTS).
+    ///
+    /// `#[derive(VastEnum)]` uses the variant's identifier, unless it has a
+    /// `#[vast_enum(rename = "..")]` attribute.
+    const NAMES: &'static [&'static str];
+}
+
 /// A wrapper for traits that valid enum reprs implement.
-pub trait EnumRepr<Enum>: Copy + Default + Hash + Eq + Ord + TryInto<Enum> {}
+///
+/// `Default` isn't required, so types without a zero value, such as
+/// [`NonZeroU32`](core::num::NonZeroU32), can be used as well. [`VastEnum`] only implements
+/// `Default` when `Repr` does.
+pub trait EnumRepr<Enum>: Copy + Hash + Eq + Ord + TryInto<Enum> {}
 
-impl<Enum, Repr> EnumRepr<Enum> for Repr where Repr: Copy + Default + Hash + Eq + Ord + TryInto<Enum>
-{}
+impl<Enum, Repr> EnumRepr<Enum> for Repr where Repr: Copy + Hash + Eq + Ord + TryInto<Enum> {}
@@ -0,0 +1,180 @@
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
+
+/// An integer type whose values are a range of a primitive integer it's stored as, such as a
+/// 3-bit `u3` stored in a `u8`, or a [`NonZeroU32`], that can be used as a
+/// [`VastEnum`](crate::VastEnum) repr.
+///
+/// `#[derive(VastEnum)]` uses a narrow repr when the enum has a `#[vast_enum(repr = ..)]`
+/// attribute. The enum's own `#[repr(..)]` must then be the storage type, and every discriminant
+/// must be between [`MIN`](NarrowRepr::MIN) and [`MAX`](NarrowRepr::MAX), which is checked at
+/// compile time. The generated `Vast{Enum}` alias wraps the narrow type, so it can't even hold
+/// out-of-range values.
+///
+/// With a `NonZero*` repr, `Option<VastEnum<..>>` uses zero to represent `None` and stays the size
+/// of the integer, which suits protocols that reserve zero for "absent":
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use core::mem::size_of;
+/// use core::num::NonZeroU32;
+/// use vast_enum::VastEnum;
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u32)]
+/// #[vast_enum(repr = NonZeroU32)]
+/// enum Codec {
+///     Opus = 1,
+///     Vorbis = 2,
+/// }
+///
+/// assert_eq!(size_of::<Option<VastCodec>>(), 4);
+///
+/// let codec = NonZeroU32::new(2).map(VastCodec::from_int);
+/// assert_eq!(codec.and_then(|codec| codec.variant()), Some(Codec::Vorbis));
+/// assert_eq!(VastCodec::OPUS.int().get(), 1);
+/// # }
+/// ```
+///
+/// With the `arbitrary-int` feature, the odd-width integers of the
+/// [arbitrary-int](https://crates.io/crates/arbitrary-int) crate are supported as well:
+///
+/// ```
+/// # #[cfg(all(feature = "derive", feature = "arbitrary-int"))]
+/// # {
+/// use arbitrary_int::u3;
+/// use vast_enum::VastEnum;
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// #[vast_enum(repr = u3)]
+/// enum Priority {
+///     Low = 0,
+///     Normal = 3,
+///     High = 7,
+/// }
+///
+/// let priority: VastPriority = VastEnum::from_int(u3::new(3));
+/// assert_eq!(priority.variant(), Some(Priority::Normal));
+/// assert_eq!(VastPriority::HIGH.int(), u3::new(7));
+/// assert_eq!(VastEnum::<Priority>::from_int(u3::new(5)).variant(), None);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// use core::num::NonZeroU8;
+/// use vast_enum::VastEnum;
+///
+/// #[derive(VastEnum)]
+/// #[repr(u8)]
+/// #[vast_enum(repr = NonZeroU8)]
+/// enum Absent {
+///     None = 0,
+///     Some = 1,
+/// }
+/// ```
+///
+/// The derived per-variant constants are built through [`NarrowConst`], so a type used with
+/// `#[vast_enum(repr = ..)]` must implement that as well.
+pub trait NarrowRepr: Copy {
+    /// The primitive integer the value is stored as.
+    type Storage: Copy;
+
+    /// The smallest value, as the storage type.
+    const MIN: Self::Storage;
+
+    /// The largest value, as the storage type.
+    const MAX: Self::Storage;
+
+    /// Converts from the storage type, if `storage` is between [`MIN`](NarrowRepr::MIN) and
+    /// [`MAX`](NarrowRepr::MAX).
+    fn try_from_storage(storage: Self::Storage) -> Option<Self>;
+
+    /// Converts to the storage type.
+    fn storage(self) -> Self::Storage;
+}
+
+/// A [`NarrowRepr`] that can be created from its storage value in a `const` context.
+///
+/// `#[derive(VastEnum)]` builds the per-variant constants, such as `VastCodec::OPUS`, from
+/// `<Repr as NarrowConst<{ discriminant as u128 }>>::VALUE`, since trait methods can't be called
+/// in constants. This works for any path to the type, including type aliases.
+///
+/// `STORAGE` is the storage value converted to `u128`. Evaluating `VALUE` must fail, such as with
+/// a `panic!`, if `STORAGE` is out of range, which only happens if the range check of the derive
+/// was bypassed.
+pub trait NarrowConst<const STORAGE: u128>: NarrowRepr {
+    /// The value whose storage is `STORAGE`.
+    const VALUE: Self;
+}
+
+macro_rules! impl_narrow_repr_non_zero {
+    ($($non_zero:ty => $storage:ty),*) => {
+        $(
+            impl NarrowRepr for $non_zero {
+                type Storage = $storage;
+
+                const MIN: $storage = 1;
+                const MAX: $storage = <$storage>::MAX;
+
+                fn try_from_storage(storage: $storage) -> Option<Self> {
+                    <$non_zero>::new(storage)
+                }
+
+                fn storage(self) -> $storage {
+                    self.get()
+                }
+            }
+
+            impl<const STORAGE: u128> NarrowConst<STORAGE> for $non_zero {
+                const VALUE: Self = match <$non_zero>::new(STORAGE as $storage) {
+                    Some(value) if STORAGE <= <$storage>::MAX as u128 => value,
+                    _ => panic!("the storage value is out of range"),
+                };
+            }
+        )*
+    };
+}
+
+impl_narrow_repr_non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128
+);
+
+#[cfg(feature = "arbitrary-int")]
+macro_rules! impl_narrow_repr_arbitrary_int {
+    ($($storage:ty),*) => {
+        $(
+            impl<const BITS: usize> NarrowRepr for arbitrary_int::UInt<$storage, BITS> {
+                type Storage = $storage;
+
+                const MIN: $storage = 0;
+                const MAX: $storage = <Self as arbitrary_int::traits::Integer>::MAX.value();
+
+                fn try_from_storage(storage: $storage) -> Option<Self> {
+                    Self::try_new(storage).ok()
+                }
+
+                fn storage(self) -> $storage {
+                    self.value()
+                }
+            }
+
+            impl<const STORAGE: u128, const BITS: usize> NarrowConst<STORAGE>
+                for arbitrary_int::UInt<$storage, BITS>
+            {
+                const VALUE: Self = if STORAGE <= <$storage>::MAX as u128 {
+                    Self::new(STORAGE as $storage)
+                } else {
+                    panic!("the storage value is out of range")
+                };
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "arbitrary-int")]
+impl_narrow_repr_arbitrary_int!(u8, u16, u32, u64, u128);
@@ -0,0 +1,766 @@
+//! Alternative serde representations, for use with `#[serde(with = "...")]`.
+//!
+//! By default, a [`VastEnum`] serializes and deserializes exactly like its `Repr`, so it can
+//! replace a plain integer field without changing the serialized data.
+
+#[cfg(feature = "alloc")]
+use crate::VastStrEnum;
+use crate::{EnumRepr, Variants, VastEnum};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+use serde::de::{self, IntoDeserializer, Visitor};
+use serde::{ser, Deserialize, Deserializer, Serializer};
+
+pub mod named {
+    //! Writes valid values as their variant name and unknown values as their integer discriminant.
+    //!
+    //! This only applies to human-readable formats, like JSON, YAML and TOML. Other formats always
+    //! use the integer discriminant, so they stay compact. With a repr that serializes as a string,
+    //! such as `char`, unknown values are written as that string instead, prefixed with `#` if it's
+    //! a variant name. This keeps an unknown `'B'` from being read back as a variant named `B`.
+    //!
+    //! When deserializing from a human-readable format, either a variant name or an integer is
+    //! accepted, and strings that contain an integer are treated as that integer, unless they're a
+    //! variant name. Strings that deserialize as the repr, like those written for unknown `char`
+    //! values, are treated as that discriminant, with or without a `#` prefix, and so are the byte
+    //! arrays written for [`ByteTag`](crate::ByteTag) values that aren't printable. [`deserialize`]
+    //! rejects names that don't match any variant, while [`deserialize_or`] maps them to a fallback
+    //! discriminant.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! # #[cfg(feature = "derive")]
+    //! # {
+    //! use serde::{Deserialize, Deserializer, Serialize};
+    //! use vast_enum::VastEnum;
+    //!
+    //! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    //! #[repr(u8)]
+    //! enum Color {
+    //!     Red = 0,
+    //!     Yellow = 1,
+    //!     Green = 2,
+    //! }
+    //!
+    //! fn color_or_unknown<'de, D: Deserializer<'de>>(deserializer: D) -> Result<VastColor, D::Error> {
+    //!     vast_enum::serde::named::deserialize_or(deserializer, 0xFF)
+    //! }
+    //!
+    //! #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    //! struct Config {
+    //!     #[serde(with = "vast_enum::serde::named")]
+    //!     background: VastColor,
+    //!     #[serde(
+    //!         serialize_with = "vast_enum::serde::named::serialize",
+    //!         deserialize_with = "color_or_unknown"
+    //!     )]
+    //!     foreground: VastColor,
+    //! }
+    //!
+    //! let config = Config {
+    //!     background: VastEnum::from_variant(Color::Green),
+    //!     foreground: VastEnum::from_int(7),
+    //! };
+    //! let json = serde_json::to_string(&config).unwrap();
+    //! assert_eq!(json, r#"{"background":"Green","foreground":7}"#);
+    //! assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    //!
+    //! let config: Config = serde_json::from_str(r#"{"background":1,"foreground":"Blue"}"#).unwrap();
+    //! assert_eq!(config.background.variant(), Some(Color::Yellow));
+    //! assert_eq!(config.foreground.int(), 0xFF);
+    //!
+    //! let error = serde_json::from_str::<Config>(r#"{"background":"Blue","foreground":0}"#)
+    //!     .unwrap_err()
+    //!     .to_string();
+    //! assert!(error.starts_with("unknown variant `Blue`, expected one of `Red`, `Yellow`, `Green`"));
+    //! # }
+    //! ```
+
+    use super::NameOrInt;
+    use crate::{EnumRepr, Variants, VastEnum};
+    use serde::de::{value, DeserializeOwned};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes a [`VastEnum`] as its variant name, or as its integer discriminant if it's not
+    /// valid or the format isn't human-readable.
+    ///
+    /// The repr must be deserializable to check whether an unknown value would be written as a
+    /// variant name.
+    pub fn serialize<Enum, Repr, S>(
+        enum_: &VastEnum<Enum, Repr>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        Enum: Variants + Copy + Into<Repr>,
+        Repr: EnumRepr<Enum> + Serialize + DeserializeOwned,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            if let Some(name) = super::name(*enum_) {
+                return serializer.serialize_str(name);
+            }
+
+            let int = enum_.int();
+            let colliding = Enum::NAMES.iter().find(|name| {
+                Repr::deserialize(value::StrDeserializer::<value::Error>::new(name)).ok()
+                    == Some(int)
+            });
+            if let Some(name) = colliding {
+                return super::serialize_escaped::<Enum, S>(name, serializer);
+            }
+        }
+
+        enum_.int().serialize(serializer)
+    }
+
+    /// Deserializes a [`VastEnum`] from a variant name or an integer discriminant, rejecting
+    /// unknown names.
+    pub fn deserialize<'de, Enum, Repr, D>(
+        deserializer: D,
+    ) -> Result<VastEnum<Enum, Repr>, D::Error>
+    where
+        Enum: Variants + Copy + Into<Repr>,
+        Repr: EnumRepr<Enum> + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        NameOrInt::deserialize(deserializer, None, None)
+    }
+
+    /// Deserializes a [`VastEnum`] from a variant name or an integer discriminant, mapping unknown
+    /// names to `fallback`.
+    pub fn deserialize_or<'de, Enum, Repr, D>(
+        deserializer: D,
+        fallback: Repr,
+    ) -> Result<VastEnum<Enum, Repr>, D::Error>
+    where
+        Enum: Variants + Copy + Into<Repr>,
+        Repr: EnumRepr<Enum> + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        NameOrInt::deserialize(deserializer, Some(fallback), None)
+    }
+}
+
+pub mod strict {
+    //! Serializes like the default representation, but fails to deserialize invalid discriminants.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! # #[cfg(feature = "derive")]
+    //! # {
+    //! use serde::{Deserialize, Serialize};
+    //! use vast_enum::VastEnum;
+    //!
+    //! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    //! #[repr(u8)]
+    //! enum Color {
+    //!     Red = 0,
+    //!     Yellow = 1,
+    //!     Green = 2,
+    //! }
+    //!
+    //! #[derive(Debug, Serialize, Deserialize)]
+    //! struct Config {
+    //!     #[serde(with = "vast_enum::serde::strict")]
+    //!     background: VastColor,
+    //!     foreground: VastColor,
+    //! }
+    //!
+    //! let config: Config = serde_json::from_str(r#"{"background":2,"foreground":7}"#).unwrap();
+    //! assert_eq!(config.background.variant(), Some(Color::Green));
+    //! assert_eq!(config.foreground.int(), 7);
+    //!
+    //! let error = serde_json::from_str::<Config>(r#"{"background":7,"foreground":2}"#)
+    //!     .unwrap_err()
+    //!     .to_string();
+    //! assert!(error.starts_with("invalid discriminant 7 for enum"));
+    //! # }
+    //! ```
+
+    use crate::{EnumRepr, VastEnum};
+    use core::fmt::Display;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes a [`VastEnum`] as its integer discriminant.
+    pub fn serialize<Enum, Repr, S>(
+        enum_: &VastEnum<Enum, Repr>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        Enum: Into<Repr>,
+        Repr: EnumRepr<Enum> + Serialize,
+        S: Serializer,
+    {
+        enum_.serialize(serializer)
+    }
+
+    /// Deserializes a [`VastEnum`] from its integer discriminant, failing if it's not a valid
+    /// value for the wrapped enum type.
+    pub fn deserialize<'de, Enum, Repr, D>(
+        deserializer: D,
+    ) -> Result<VastEnum<Enum, Repr>, D::Error>
+    where
+        Enum: Into<Repr>,
+        Repr: EnumRepr<Enum> + Deserialize<'de> + Display,
+        D: Deserializer<'de>,
+    {
+        let enum_ = VastEnum::<Enum, Repr>::deserialize(deserializer)?;
+        match enum_.try_variant() {
+            Ok(_) => Ok(enum_),
+            Err(error) => Err(D::Error::custom(error)),
+        }
+    }
+}
+
+pub mod named_keys {
+    //! Like [`named`](super::named), but for the keys of a map such as a `HashMap` or `BTreeMap`.
+    //!
+    //! In human-readable formats, valid keys are written as their variant name and unknown keys
+    //! with their repr's `Display` impl, such as an integer discriminant converted to a string,
+    //! since formats like JSON and TOML only allow string keys. If that string is a variant name,
+    //! it's prefixed with `#`. When deserializing, strings that aren't variant names are parsed
+    //! with the repr's `FromStr` impl, which must accept what `Display` writes, and plain integer
+    //! keys are accepted too. Unknown names are rejected.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! # #[cfg(feature = "derive")]
+    //! # {
+    //! use serde::{Deserialize, Serialize};
+    //! use std::collections::BTreeMap;
+    //! use vast_enum::VastEnum;
+    //!
+    //! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    //! #[repr(u8)]
+    //! enum Message {
+    //!     Ping = 0,
+    //!     Data = 1,
+    //! }
+    //!
+    //! #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    //! struct Stats {
+    //!     #[serde(with = "vast_enum::serde::named_keys")]
+    //!     counts: BTreeMap<VastMessage, u64>,
+    //! }
+    //!
+    //! let mut counts = BTreeMap::new();
+    //! counts.insert(VastEnum::from_variant(Message::Data), 10);
+    //! counts.insert(VastEnum::from_int(9), 2);
+    //! let stats = Stats { counts };
+    //!
+    //! let json = serde_json::to_string(&stats).unwrap();
+    //! assert_eq!(json, r#"{"counts":{"Data":10,"9":2}}"#);
+    //! assert_eq!(serde_json::from_str::<Stats>(&json).unwrap(), stats);
+    //! assert_eq!(
+    //!     serde_json::from_str::<Stats>(r#"{"counts":{"1":10,"9":2}}"#).unwrap(),
+    //!     stats
+    //! );
+    //! # }
+    //! ```
+
+    use super::NameOrInt;
+    use crate::{EnumRepr, Variants, VastEnum};
+    use core::fmt::{Display, Formatter, Write};
+    use core::marker::PhantomData;
+    use core::str::FromStr;
+    use serde::de::{MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes a map, writing its keys as variant names or stringified integer discriminants.
+    pub fn serialize<'a, Map, Enum, Repr, Value, S>(
+        map: &'a Map,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        &'a Map: IntoIterator<Item = (&'a VastEnum<Enum, Repr>, &'a Value)>,
+        Enum: Variants + Copy + Into<Repr> + 'a,
+        Repr: EnumRepr<Enum> + Serialize + Display + 'a,
+        Value: Serialize + 'a,
+        S: Serializer,
+    {
+        serializer.collect_map(map.into_iter().map(|(key, value)| (Key(*key), value)))
+    }
+
+    /// Deserializes a map whose keys are variant names or integer discriminants, rejecting unknown
+    /// names.
+    pub fn deserialize<'de, Map, Enum, Repr, Value, D>(deserializer: D) -> Result<Map, D::Error>
+    where
+        Map: Default + Extend<(VastEnum<Enum, Repr>, Value)>,
+        Enum: Variants + Copy + Into<Repr>,
+        Repr: EnumRepr<Enum> + Deserialize<'de> + FromStr,
+        Value: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+
+    struct Key<Enum, Repr>(VastEnum<Enum, Repr>)
+    where
+        Enum: Into<Repr>,
+        Repr: EnumRepr<Enum>;
+
+    impl<Enum, Repr> Serialize for Key<Enum, Repr>
+    where
+        Enum: Variants + Copy + Into<Repr>,
+        Repr: EnumRepr<Enum> + Serialize + Display,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if !serializer.is_human_readable() {
+                return self.0.int().serialize(serializer);
+            }
+
+            if let Some(name) = super::name(self.0) {
+                return serializer.serialize_str(name);
+            }
+
+            let int = self.0.int();
+            match Enum::NAMES.iter().find(|name| displays_as(&int, name)) {
+                Some(name) => super::serialize_escaped::<Enum, S>(name, serializer),
+                None => serializer.collect_str(&int),
+            }
+        }
+    }
+
+    impl<'de, Enum, Repr> Deserialize<'de> for Key<Enum, Repr>
+    where
+        Enum: Variants + Copy + Into<Repr>,
+        Repr: EnumRepr<Enum> + Deserialize<'de> + FromStr,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            NameOrInt::deserialize(deserializer, None, Some(parse_key)).map(Key)
+        }
+    }
+
+    fn parse_key<Repr: FromStr>(s: &str) -> Option<Repr> {
+        s.parse().ok()
+    }
+
+    /// Returns whether `value` is displayed as exactly `s`, without allocating.
+    fn displays_as(value: &impl Display, s: &str) -> bool {
+        /// Consumes the written text from the start of the string, failing when it doesn't match.
+        struct Prefix<'a>(&'a str);
+
+        impl Write for Prefix<'_> {
+            fn write_str(&mut self, text: &str) -> core::fmt::Result {
+                match self.0.strip_prefix(text) {
+                    Some(rest) => {
+                        self.0 = rest;
+                        Ok(())
+                    }
+                    None => Err(core::fmt::Error),
+                }
+            }
+        }
+
+        let mut prefix = Prefix(s);
+        write!(prefix, "{}", value).is_ok() && prefix.0.is_empty()
+    }
+
+    struct MapVisitor<Map, Enum, Repr, Value>(PhantomData<(Map, Enum, Repr, Value)>);
+
+    impl<'de, Map, Enum, Repr, Value> Visitor<'de> for MapVisitor<Map, Enum, Repr, Value>
+    where
+        Map: Default + Extend<(VastEnum<Enum, Repr>, Value)>,
+        Enum: Variants + Copy + Into<Repr>,
+        Repr: EnumRepr<Enum> + Deserialize<'de> + FromStr,
+        Value: Deserialize<'de>,
+    {
+        type Value = Map;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            f.write_str("a map")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Map, A::Error> {
+            let mut map = Map::default();
+            while let Some((Key(key), value)) = access.next_entry::<Key<Enum, Repr>, Value>()? {
+                map.extend(Some((key, value)));
+            }
+
+            Ok(map)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub mod borrowed {
+    //! Serializes a [`VastStrEnum`] like the default representation, but borrows unknown strings
+    //! from the input when deserializing it, instead of copying them.
+    //!
+    //! Borrowing only works with formats that can hand out strings from their input, like
+    //! `serde_json::from_str` for strings without escapes. Other strings are still copied.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! # #[cfg(feature = "derive")]
+    //! # {
+    //! use serde::{Deserialize, Serialize};
+    //! use std::borrow::Cow;
+    //! use vast_enum::{VastEnum, VastStrEnum};
+    //!
+    //! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    //! #[repr(u8)]
+    //! enum Method {
+    //!     #[vast_enum(rename = "GET")]
+    //!     Get,
+    //!     #[vast_enum(rename = "POST")]
+    //!     Post,
+    //! }
+    //!
+    //! #[derive(Debug, Serialize, Deserialize)]
+    //! struct Request<'a> {
+    //!     #[serde(borrow, with = "vast_enum::serde::borrowed")]
+    //!     method: VastStrEnum<'a, Method>,
+    //! }
+    //!
+    //! let json = String::from(r#"{"method":"BREW"}"#);
+    //! let request: Request = serde_json::from_str(&json).unwrap();
+    //! assert_eq!(request.method.unknown(), Some("BREW"));
+    //! assert_eq!(serde_json::to_string(&request).unwrap(), json);
+    //! # }
+    //! ```
+
+    use super::{BorrowedStrVisitor, StrVisitor};
+    use crate::{Variants, VastStrEnum};
+    use serde::{Deserializer, Serialize, Serializer};
+
+    /// Serializes a [`VastStrEnum`] as its string.
+    pub fn serialize<Enum, S>(
+        enum_: &VastStrEnum<'_, Enum>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        Enum: Variants + PartialEq,
+        S: Serializer,
+    {
+        enum_.serialize(serializer)
+    }
+
+    /// Deserializes a [`VastStrEnum`] from a string, borrowing it from the input if it's unknown
+    /// and the format allows it.
+    pub fn deserialize<'de: 'a, 'a, Enum, D>(
+        deserializer: D,
+    ) -> Result<VastStrEnum<'a, Enum>, D::Error>
+    where
+        Enum: Variants + Copy,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BorrowedStrVisitor(StrVisitor::new(false)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub mod ignore_ascii_case {
+    //! Serializes a [`VastStrEnum`] like the default representation, but ignores ASCII case when
+    //! deserializing it.
+    //!
+    //! # Example
+    //!
+    //! ```
+    //! # #[cfg(feature = "derive")]
+    //! # {
+    //! use serde::{Deserialize, Serialize};
+    //! use vast_enum::{VastEnum, VastStrEnum};
+    //!
+    //! #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    //! #[repr(u8)]
+    //! enum MediaType {
+    //!     #[vast_enum(rename = "text/plain")]
+    //!     Text,
+    //!     #[vast_enum(rename = "application/json")]
+    //!     Json,
+    //! }
+    //!
+    //! #[derive(Debug, Serialize, Deserialize)]
+    //! struct Part {
+    //!     #[serde(with = "vast_enum::serde::ignore_ascii_case")]
+    //!     content_type: VastStrEnum<'static, MediaType>,
+    //! }
+    //!
+    //! let part: Part = serde_json::from_str(r#"{"content_type":"Application/JSON"}"#).unwrap();
+    //! assert_eq!(part.content_type.variant(), Some(MediaType::Json));
+    //! assert_eq!(
+    //!     serde_json::to_string(&part).unwrap(),
+    //!     r#"{"content_type":"application/json"}"#
+    //! );
+    //!
+    //! let part: Part = serde_json::from_str(r#"{"content_type":"image/png"}"#).unwrap();
+    //! assert_eq!(part.content_type.unknown(), Some("image/png"));
+    //! # }
+    //! ```
+
+    use super::StrVisitor;
+    use crate::{Variants, VastStrEnum};
+    use serde::{Deserializer, Serialize, Serializer};
+
+    /// Serializes a [`VastStrEnum`] as its string.
+    pub fn serialize<Enum, S>(
+        enum_: &VastStrEnum<'_, Enum>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        Enum: Variants + PartialEq,
+        S: Serializer,
+    {
+        enum_.serialize(serializer)
+    }
+
+    /// Deserializes a [`VastStrEnum`] from a string, ignoring ASCII case when matching variant
+    /// names.
+    pub fn deserialize<'de, 'a, Enum, D>(deserializer: D) -> Result<VastStrEnum<'a, Enum>, D::Error>
+    where
+        Enum: Variants + Copy,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor::new(true))
+    }
+}
+
+/// Returns the name of the variant a [`VastEnum`] holds, if it's valid.
+fn name<Enum, Repr>(enum_: VastEnum<Enum, Repr>) -> Option<&'static str>
+where
+    Enum: Variants + Copy + Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    Enum::VARIANTS
+        .iter()
+        .position(|&variant| variant.into() == enum_.int())
+        .map(|i| Enum::NAMES[i])
+}
+
+/// Writes an unknown value whose string form is the variant name `name` as `#name`.
+///
+/// Fails if that's a variant name as well, since the value couldn't be read back.
+fn serialize_escaped<Enum: Variants, S: Serializer>(
+    name: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if Enum::NAMES
+        .iter()
+        .any(|n| n.strip_prefix('#') == Some(name))
+    {
+        return Err(ser::Error::custom(format_args!(
+            "the unknown value `{}` can't be written, since both `{}` and `#{}` are variant names",
+            name, name, name
+        )));
+    }
+
+    serializer.collect_str(&format_args!("#{}", name))
+}
+
+/// Returns the variant with the given name.
+fn variant<Enum: Variants + Copy>(name: &str) -> Option<Enum> {
+    Enum::NAMES
+        .iter()
+        .position(|&n| n == name)
+        .map(|i| Enum::VARIANTS[i])
+}
+
+/// A visitor that accepts either a variant name or an integer discriminant.
+struct NameOrInt<Enum, Repr> {
+    fallback: Option<Repr>,
+    /// Parses strings that aren't variant names, for map keys whose unknown values are written with
+    /// `Display`. Other strings are deserialized as the repr or parsed as integers.
+    parse_key: Option<fn(&str) -> Option<Repr>>,
+    _enum: PhantomData<Enum>,
+}
+
+impl<'de, Enum, Repr> NameOrInt<Enum, Repr>
+where
+    Enum: Variants + Copy + Into<Repr>,
+    Repr: EnumRepr<Enum> + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+        fallback: Option<Repr>,
+        parse_key: Option<fn(&str) -> Option<Repr>>,
+    ) -> Result<VastEnum<Enum, Repr>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(NameOrInt {
+                fallback,
+                parse_key,
+                _enum: PhantomData,
+            })
+        } else {
+            Repr::deserialize(deserializer).map(VastEnum::from_int)
+        }
+    }
+
+    fn int<T, E>(value: T) -> Result<VastEnum<Enum, Repr>, E>
+    where
+        T: IntoDeserializer<'de, E>,
+        E: de::Error,
+    {
+        Repr::deserialize(value.into_deserializer()).map(VastEnum::from_int)
+    }
+
+    /// Handles a string that's neither a variant name nor a discriminant.
+    fn unknown<E: de::Error>(self, v: &str) -> Result<VastEnum<Enum, Repr>, E> {
+        match self.fallback {
+            Some(fallback) => Ok(VastEnum::from_int(fallback)),
+            None => Err(E::unknown_variant(v, Enum::NAMES)),
+        }
+    }
+}
+
+impl<'de, Enum, Repr> Visitor<'de> for NameOrInt<Enum, Repr>
+where
+    Enum: Variants + Copy + Into<Repr>,
+    Repr: EnumRepr<Enum> + Deserialize<'de>,
+{
+    type Value = VastEnum<Enum, Repr>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("a variant name or an integer")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Self::int(v)
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        Self::int(v)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Self::int(v)
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        Self::int(v)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        // Names come first, since a variant can be renamed to a string that looks like an integer.
+        if let Some(variant) = variant::<Enum>(v) {
+            return Ok(VastEnum::from_variant(variant));
+        }
+        if let Some(parse) = self.parse_key {
+            // `named_keys` prefixes unknown keys with `#` if they'd be written as a variant name.
+            return match parse(v).or_else(|| v.strip_prefix('#').and_then(parse)) {
+                Some(int) => Ok(VastEnum::from_int(int)),
+                None => self.unknown(v),
+            };
+        }
+        // Reprs like `char` and `ByteTag` serialize as strings, so `serialize` writes their unknown
+        // values as strings too.
+        if let Ok(int) = Repr::deserialize(de::value::StrDeserializer::<E>::new(v)) {
+            return Ok(VastEnum::from_int(int));
+        }
+        if let Ok(int) = v.parse::<u64>() {
+            return Self::int(int);
+        }
+        if let Ok(int) = v.parse::<i64>() {
+            return Self::int(int);
+        }
+        if let Ok(int) = v.parse::<u128>() {
+            return Self::int(int);
+        }
+        if let Ok(int) = v.parse::<i128>() {
+            return Self::int(int);
+        }
+        // `serialize` prefixes unknown values with `#` if they'd be written as a variant name.
+        let escaped = v
+            .strip_prefix('#')
+            .map(|v| Repr::deserialize(de::value::StrDeserializer::<E>::new(v)));
+        if let Some(Ok(int)) = escaped {
+            return Ok(VastEnum::from_int(int));
+        }
+
+        self.unknown(v)
+    }
+
+    // `ByteTag` writes unknown values that aren't printable as bytes.
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Repr::deserialize(de::value::BytesDeserializer::new(v)).map(VastEnum::from_int)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        Repr::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(VastEnum::from_int)
+    }
+}
+
+/// A visitor for a [`VastStrEnum`], which copies unknown strings.
+#[cfg(feature = "alloc")]
+pub(crate) struct StrVisitor<'a, Enum> {
+    ignore_ascii_case: bool,
+    _enum: PhantomData<VastStrEnum<'a, Enum>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Enum> StrVisitor<'a, Enum>
+where
+    Enum: Variants + Copy,
+{
+    pub(crate) fn new(ignore_ascii_case: bool) -> Self {
+        StrVisitor {
+            ignore_ascii_case,
+            _enum: PhantomData,
+        }
+    }
+
+    fn enum_<'b>(&self, value: Cow<'b, str>) -> VastStrEnum<'b, Enum> {
+        if self.ignore_ascii_case {
+            VastStrEnum::new_ignore_ascii_case(value)
+        } else {
+            VastStrEnum::new(value)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, 'a, Enum> Visitor<'de> for StrVisitor<'a, Enum>
+where
+    Enum: Variants + Copy,
+{
+    type Value = VastStrEnum<'a, Enum>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.enum_(Cow::Borrowed(v)).into_owned())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(self.enum_(Cow::Owned(v)))
+    }
+}
+
+/// A visitor for a [`VastStrEnum`], which borrows unknown strings from the input when it can.
+#[cfg(feature = "alloc")]
+struct BorrowedStrVisitor<'a, Enum>(StrVisitor<'a, Enum>);
+
+#[cfg(feature = "alloc")]
+impl<'de: 'a, 'a, Enum> Visitor<'de> for BorrowedStrVisitor<'a, Enum>
+where
+    Enum: Variants + Copy,
+{
+    type Value = VastStrEnum<'a, Enum>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.expecting(f)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(self.0.enum_(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.0.visit_str(v)
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        self.0.visit_string(v)
+    }
+}
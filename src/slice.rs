@@ -0,0 +1,149 @@
+use crate::{EnumRepr, TableRepr, ValidityTable, VastEnum};
+use core::iter::FusedIterator;
+use core::slice::Chunks;
+use derivative::Derivative;
+
+/// The number of values checked together, one bit each in a `u64` mask.
+const CHUNK: usize = 64;
+
+/// Bulk validity checks for slices of [`VastEnum`]s, backed by the enum's [`ValidityTable`].
+///
+/// The slice is checked in chunks of 64 values, each of which gives a mask of the invalid ones, so
+/// counting and searching don't branch on individual values. The lookups themselves are scalar and
+/// only use `core`, so this is available in `no_std` builds as well.
+///
+/// This needs the enum to implement [`ValidityTable`] for the slice's repr, which
+/// `#[derive(VastEnum)]` only does for primitive integer reprs. Slices of enums declared with
+/// literals, such as `char` or [`FourCc`](crate::FourCc) tags, or with a `NonZero*` or other
+/// [`NarrowRepr`](crate::NarrowRepr), can be checked one value at a time with
+/// [`VastEnum::is_valid`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{VastEnum, VastSlice};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop = 0x00,
+///     Load = 0x10,
+///     Store = 0x11,
+/// }
+///
+/// let program: Vec<VastOpcode> = [0x10, 0x00, 0x42, 0x11, 0xFF]
+///     .iter()
+///     .map(|&int| VastEnum::from_int(int))
+///     .collect();
+///
+/// assert!(!program.all_valid());
+/// assert_eq!(program.count_invalid(), 2);
+/// assert_eq!(program.first_invalid_index(), Some(2));
+/// assert_eq!(program.invalid_indices().collect::<Vec<_>>(), [2, 4]);
+/// # }
+/// ```
+pub trait VastSlice<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    /// Returns whether every discriminant in the slice is a valid value for the enum type.
+    fn all_valid(&self) -> bool;
+
+    /// Returns the number of discriminants that aren't valid values for the enum type.
+    fn count_invalid(&self) -> usize;
+
+    /// Returns the index of the first discriminant that isn't a valid value for the enum type.
+    fn first_invalid_index(&self) -> Option<usize>;
+
+    /// Returns an iterator over the indices of the discriminants that aren't valid values for the
+    /// enum type, in ascending order.
+    fn invalid_indices(&self) -> InvalidIndices<'_, Enum, Repr>;
+}
+
+impl<Enum, Repr> VastSlice<Enum, Repr> for [VastEnum<Enum, Repr>]
+where
+    Enum: Into<Repr> + ValidityTable<Repr>,
+    Repr: EnumRepr<Enum> + TableRepr + 'static,
+{
+    fn all_valid(&self) -> bool {
+        self.chunks(CHUNK).all(|chunk| invalid_mask(chunk) == 0)
+    }
+
+    fn count_invalid(&self) -> usize {
+        self.chunks(CHUNK)
+            .map(|chunk| invalid_mask(chunk).count_ones() as usize)
+            .sum()
+    }
+
+    fn first_invalid_index(&self) -> Option<usize> {
+        self.invalid_indices().next()
+    }
+
+    fn invalid_indices(&self) -> InvalidIndices<'_, Enum, Repr> {
+        InvalidIndices {
+            chunks: self.chunks(CHUNK),
+            next_offset: 0,
+            offset: 0,
+            mask: 0,
+        }
+    }
+}
+
+/// An iterator over the indices of invalid discriminants in a slice, created by
+/// [`VastSlice::invalid_indices`].
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct InvalidIndices<'a, Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    chunks: Chunks<'a, VastEnum<Enum, Repr>>,
+    next_offset: usize,
+    offset: usize,
+    mask: u64,
+}
+
+impl<Enum, Repr> Iterator for InvalidIndices<'_, Enum, Repr>
+where
+    Enum: Into<Repr> + ValidityTable<Repr>,
+    Repr: EnumRepr<Enum> + TableRepr + 'static,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.mask == 0 {
+            let chunk = self.chunks.next()?;
+            self.offset = self.next_offset;
+            self.next_offset += chunk.len();
+            self.mask = invalid_mask(chunk);
+        }
+
+        let index = self.offset + self.mask.trailing_zeros() as usize;
+        self.mask &= self.mask - 1;
+        Some(index)
+    }
+}
+
+impl<Enum, Repr> FusedIterator for InvalidIndices<'_, Enum, Repr>
+where
+    Enum: Into<Repr> + ValidityTable<Repr>,
+    Repr: EnumRepr<Enum> + TableRepr + 'static,
+{
+}
+
+/// Returns a mask with bit `i` set if `chunk[i]` is invalid. `chunk` has at most 64 values.
+#[inline]
+fn invalid_mask<Enum, Repr>(chunk: &[VastEnum<Enum, Repr>]) -> u64
+where
+    Enum: Into<Repr> + ValidityTable<Repr>,
+    Repr: EnumRepr<Enum> + TableRepr + 'static,
+{
+    let table = Enum::DISCRIMINANTS;
+    chunk.iter().enumerate().fold(0, |mask, (i, value)| {
+        mask | u64::from(!table.contains(value.int())) << i
+    })
+}
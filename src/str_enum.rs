@@ -0,0 +1,220 @@
+use crate::Variants;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::convert::Infallible;
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
+
+/// A string-valued enum that can also hold unknown strings, such as an HTTP method, a MIME type or
+/// the `"type"` field of a JSON object.
+///
+/// The strings of the known variants are their [`Variants::NAMES`], which `#[derive(VastEnum)]`
+/// takes from the variant identifiers or from `#[vast_enum(rename = "..")]` attributes. Unknown
+/// strings are kept as a [`Cow`], so they can borrow from the input they were parsed from.
+///
+/// [`new`](VastStrEnum::new) matches names exactly, while
+/// [`new_ignore_ascii_case`](VastStrEnum::new_ignore_ascii_case) ignores ASCII case. Either way, an
+/// unknown string is kept as it was given.
+///
+/// With the `serde` feature, this serializes as its string, and deserializes by matching names
+/// exactly. Like `Cow<str>`, it copies unknown strings when deserializing, so it can be used with
+/// `DeserializeOwned` APIs such as `serde_json::from_reader`. The
+/// [`serde::borrowed`](crate::serde::borrowed) representation borrows them from the input instead,
+/// and [`serde::ignore_ascii_case`](crate::serde::ignore_ascii_case) ignores ASCII case.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{VastEnum, VastStrEnum};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum Method {
+///     #[vast_enum(rename = "GET")]
+///     Get,
+///     #[vast_enum(rename = "POST")]
+///     Post,
+/// }
+///
+/// let method = VastStrEnum::<Method>::new("POST");
+/// assert_eq!(method.variant(), Some(Method::Post));
+/// assert_eq!(format!("{:?}", method), r#"VastStrEnum("POST": Post)"#);
+///
+/// let method = VastStrEnum::<Method>::new("get");
+/// assert!(!method.is_valid());
+/// assert_eq!(method.unknown(), Some("get"));
+///
+/// let method = VastStrEnum::<Method>::new_ignore_ascii_case("get");
+/// assert_eq!(method.variant(), Some(Method::Get));
+/// assert_eq!(method.as_str(), "GET");
+///
+/// let method: VastStrEnum<Method> = "BREW".parse().unwrap();
+/// assert_eq!(method.to_string(), "BREW");
+/// # }
+/// ```
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct VastStrEnum<'a, Enum>(Value<'a, Enum>);
+
+#[derive(Clone, Hash, Eq, PartialEq)]
+enum Value<'a, Enum> {
+    Known(Enum),
+    Unknown(Cow<'a, str>),
+}
+
+impl<'a, Enum> VastStrEnum<'a, Enum> {
+    /// Creates a [`VastStrEnum`] from an enum variant.
+    pub const fn from_variant(variant: Enum) -> Self {
+        VastStrEnum(Value::Known(variant))
+    }
+
+    /// Creates a [`VastStrEnum`] from a string, which must match a variant's name exactly to be
+    /// known.
+    pub fn new(value: impl Into<Cow<'a, str>>) -> Self
+    where
+        Enum: Variants + Copy,
+    {
+        Self::find(value.into(), |name, value| name == value)
+    }
+
+    /// Creates a [`VastStrEnum`] from a string, which must match a variant's name ignoring ASCII
+    /// case to be known.
+    pub fn new_ignore_ascii_case(value: impl Into<Cow<'a, str>>) -> Self
+    where
+        Enum: Variants + Copy,
+    {
+        Self::find(value.into(), str::eq_ignore_ascii_case)
+    }
+
+    fn find(value: Cow<'a, str>, matches: impl Fn(&str, &str) -> bool) -> Self
+    where
+        Enum: Variants + Copy,
+    {
+        match Enum::NAMES.iter().position(|name| matches(name, &value)) {
+            Some(i) => VastStrEnum(Value::Known(Enum::VARIANTS[i])),
+            None => VastStrEnum(Value::Unknown(value)),
+        }
+    }
+
+    /// Returns the enum variant, if the string is a known variant name.
+    pub fn variant(&self) -> Option<Enum>
+    where
+        Enum: Copy,
+    {
+        match self.0 {
+            Value::Known(variant) => Some(variant),
+            Value::Unknown(_) => None,
+        }
+    }
+
+    /// Returns whether the string is a known variant name.
+    pub fn is_valid(&self) -> bool {
+        matches!(self.0, Value::Known(_))
+    }
+
+    /// Returns the string if it isn't a known variant name.
+    pub fn unknown(&self) -> Option<&str> {
+        match &self.0 {
+            Value::Known(_) => None,
+            Value::Unknown(value) => Some(value),
+        }
+    }
+
+    /// Returns the string, which is the variant's name for known variants.
+    pub fn as_str(&self) -> &str
+    where
+        Enum: Variants + PartialEq,
+    {
+        match &self.0 {
+            Value::Known(variant) => {
+                let i = Enum::VARIANTS
+                    .iter()
+                    .position(|v| v == variant)
+                    .expect("the variant is missing from `Variants::VARIANTS`");
+                Enum::NAMES[i]
+            }
+            Value::Unknown(value) => value,
+        }
+    }
+
+    /// Transforms the enum variant using the provided closure. Unknown strings are kept as-is.
+    pub fn map<EnumOut>(self, f: impl FnOnce(Enum) -> EnumOut) -> VastStrEnum<'a, EnumOut> {
+        match self.0 {
+            Value::Known(variant) => VastStrEnum(Value::Known(f(variant))),
+            Value::Unknown(value) => VastStrEnum(Value::Unknown(value)),
+        }
+    }
+
+    /// Converts into a [`VastStrEnum`] that owns its unknown string, if any.
+    pub fn into_owned(self) -> VastStrEnum<'static, Enum> {
+        match self.0 {
+            Value::Known(variant) => VastStrEnum(Value::Known(variant)),
+            Value::Unknown(value) => VastStrEnum(Value::Unknown(Cow::Owned(value.into_owned()))),
+        }
+    }
+}
+
+impl<Enum> From<Enum> for VastStrEnum<'_, Enum> {
+    fn from(variant: Enum) -> Self {
+        VastStrEnum::from_variant(variant)
+    }
+}
+
+/// Matches names exactly, like [`VastStrEnum::new`].
+impl<Enum: Variants + Copy> FromStr for VastStrEnum<'static, Enum> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(VastStrEnum::new(String::from(s)))
+    }
+}
+
+impl<Enum: Debug> Debug for VastStrEnum<'_, Enum>
+where
+    Enum: Variants + PartialEq,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut tuple = f.debug_tuple("VastStrEnum");
+        match &self.0 {
+            Value::Known(variant) => {
+                tuple.field(&format_args!("{:?}: {:?}", self.as_str(), variant));
+            }
+            Value::Unknown(value) => {
+                tuple.field(value);
+            }
+        }
+
+        tuple.finish()
+    }
+}
+
+/// Formats the string.
+impl<Enum> Display for VastStrEnum<'_, Enum>
+where
+    Enum: Variants + PartialEq,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Enum> serde::Serialize for VastStrEnum<'_, Enum>
+where
+    Enum: Variants + PartialEq,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a, Enum> serde::Deserialize<'de> for VastStrEnum<'a, Enum>
+where
+    Enum: Variants + Copy,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(crate::serde::StrVisitor::new(false))
+    }
+}
@@ -0,0 +1,310 @@
+/// The valid discriminants of an enum, laid out for fast lookups.
+///
+/// This is usually built at compile time by `#[derive(VastEnum)]`, through [`ValidityTable`].
+/// Enums whose discriminants span fewer than 2<sup>16</sup> values, which includes every enum with
+/// a `u8` or `u16` repr, get a dense bitmap. Sparser enums get a perfect hash table, and only very
+/// large sparse enums for which no small table can be found fall back to a sorted list, which is
+/// searched in logarithmic time.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DiscriminantSet<Repr: 'static> {
+    /// A bitmap with one bit per value, starting at `min`.
+    Bitmap {
+        /// The value of the first bit.
+        min: Repr,
+        /// The bits, in little-endian order within each word.
+        words: &'static [u64],
+    },
+    /// A hash table without collisions, with one valid value per slot.
+    ///
+    /// A value is looked up in slot `(key * multiplier) >> shift`, where `key` is the value folded
+    /// into a `u64`. Unused slots hold a valid value that belongs to a different slot.
+    Hashed {
+        /// The odd multiplier of the hash function.
+        multiplier: u64,
+        /// The shift of the hash function, which is `64 - log2(slots.len())`.
+        shift: u32,
+        /// The slots, whose length is a power of two.
+        slots: &'static [Repr],
+    },
+    /// Every valid value, in ascending order.
+    Sorted(&'static [Repr]),
+}
+
+impl<Repr: TableRepr> DiscriminantSet<Repr> {
+    /// Returns whether `value` is one of the valid discriminants.
+    #[inline]
+    pub fn contains(&self, value: Repr) -> bool {
+        match *self {
+            DiscriminantSet::Bitmap { min, words } => {
+                // Values below `min` wrap around to large offsets, which are out of bounds.
+                let offset = value.wrapping_offset_from(min);
+                match words.get(offset / 64) {
+                    Some(word) => word >> (offset % 64) & 1 != 0,
+                    None => false,
+                }
+            }
+            DiscriminantSet::Hashed {
+                multiplier,
+                shift,
+                slots,
+            } => {
+                let slot = value.hash_key().wrapping_mul(multiplier) >> shift;
+                match slots.get(slot as usize) {
+                    Some(&candidate) => candidate == value,
+                    None => false,
+                }
+            }
+            DiscriminantSet::Sorted(values) => values.binary_search(&value).is_ok(),
+        }
+    }
+}
+
+/// Exposes the valid discriminants of an enum as a [`DiscriminantSet`] computed at compile time.
+///
+/// This is implemented by `#[derive(VastEnum)]`, whose `TryFrom<Repr>` impl checks this table
+/// before matching on the discriminants, so [`VastEnum::variant`](crate::VastEnum::variant) rejects
+/// invalid values in constant time, and [`VastEnum::is_valid`](crate::VastEnum::is_valid) compiles
+/// down to the lookup alone.
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{DiscriminantSet, ValidityTable, VastEnum};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u32)]
+/// enum Sparse {
+///     Low = 1,
+///     High = 0x8000_0000,
+/// }
+///
+/// let table = <Sparse as ValidityTable<u32>>::DISCRIMINANTS;
+/// assert!(matches!(table, DiscriminantSet::Hashed { .. }));
+/// assert!(table.contains(0x8000_0000));
+/// assert!(!table.contains(2));
+/// # }
+/// ```
+pub trait ValidityTable<Repr: 'static> {
+    /// The valid discriminants.
+    const DISCRIMINANTS: DiscriminantSet<Repr>;
+}
+
+/// A primitive integer that can be used in a [`DiscriminantSet`].
+pub trait TableRepr: Copy + Ord {
+    /// Returns `self - min`, wrapping around at the bounds of the type and saturating at
+    /// `usize::MAX`.
+    fn wrapping_offset_from(self, min: Self) -> usize;
+
+    /// Folds the value into the key used by [`DiscriminantSet::Hashed`].
+    fn hash_key(self) -> u64;
+}
+
+macro_rules! impl_table_repr {
+    ($($int:ty => $unsigned:ty),*) => {
+        $(
+            impl TableRepr for $int {
+                #[inline]
+                fn wrapping_offset_from(self, min: Self) -> usize {
+                    let offset = self.wrapping_sub(min) as $unsigned;
+                    #[allow(clippy::unnecessary_fallible_conversions)]
+                    core::convert::TryFrom::try_from(offset).unwrap_or(usize::MAX)
+                }
+
+                #[inline]
+                fn hash_key(self) -> u64 {
+                    // Must match the key computed by `__discriminant_set!`.
+                    let value = self as u128;
+                    if core::mem::size_of::<$int>() > 8 {
+                        value as u64 ^ (value >> 64) as u64
+                    } else {
+                        value as u64
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_table_repr!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+/// Builds a [`DiscriminantSet`] in a const context. Used by `#[derive(VastEnum)]`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __discriminant_set {
+    ($repr:ty; $($value:expr),+ $(,)?) => {{
+        const VALUES: &[$repr] = &[$($value),+];
+        const LEN: usize = VALUES.len();
+
+        const MIN: $repr = {
+            let mut min = VALUES[0];
+            let mut i = 1;
+            while i < LEN {
+                if VALUES[i] < min {
+                    min = VALUES[i];
+                }
+                i += 1;
+            }
+            min
+        };
+
+        const MAX: $repr = {
+            let mut max = VALUES[0];
+            let mut i = 1;
+            while i < LEN {
+                if VALUES[i] > max {
+                    max = VALUES[i];
+                }
+                i += 1;
+            }
+            max
+        };
+
+        // Sign extension keeps the difference correct for signed reprs.
+        const SPAN: u128 = (MAX as u128).wrapping_sub(MIN as u128);
+
+        const WORDS: usize = if SPAN < 1 << 16 { SPAN as usize / 64 + 1 } else { 0 };
+
+        const BITMAP: [u64; WORDS] = {
+            let mut words = [0; WORDS];
+            if WORDS > 0 {
+                let mut i = 0;
+                while i < LEN {
+                    let offset = (VALUES[i] as u128).wrapping_sub(MIN as u128) as usize;
+                    words[offset / 64] |= 1 << (offset % 64);
+                    i += 1;
+                }
+            }
+            words
+        };
+
+        // Must match `TableRepr::hash_key`. Keys are exact for reprs of up to 64 bits.
+        const fn hash_key(value: $repr) -> u64 {
+            let value = value as u128;
+            if core::mem::size_of::<$repr>() > 8 {
+                value as u64 ^ (value >> 64) as u64
+            } else {
+                value as u64
+            }
+        }
+
+        // Searches for a multiplier that hashes every value to its own slot, trying a few
+        // multipliers for each table size. Tables are kept to at most 4096 slots.
+        const HASH: (u64, u32) = {
+            let mut found = (0, 0);
+            let mut bits = 1;
+            while 1 << bits < LEN * 2 {
+                bits += 1;
+            }
+            let mut seen = [0u64; 4096 / 64];
+            while WORDS == 0 && found.0 == 0 && bits <= 12 {
+                let mut seed = 0u64;
+                let mut attempt = 0;
+                while found.0 == 0 && attempt < 64 {
+                    // SplitMix64, to get well-mixed odd multipliers.
+                    seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                    let mut multiplier = seed;
+                    multiplier = (multiplier ^ (multiplier >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                    multiplier = (multiplier ^ (multiplier >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                    multiplier = (multiplier ^ (multiplier >> 31)) | 1;
+
+                    let mut i = 0;
+                    while i < seen.len() {
+                        seen[i] = 0;
+                        i += 1;
+                    }
+                    let mut collision = false;
+                    let mut i = 0;
+                    while !collision && i < LEN {
+                        let slot = (hash_key(VALUES[i]).wrapping_mul(multiplier) >> (64 - bits)) as usize;
+                        collision = seen[slot / 64] & 1 << (slot % 64) != 0;
+                        seen[slot / 64] |= 1 << (slot % 64);
+                        i += 1;
+                    }
+                    if !collision {
+                        found = (multiplier, bits);
+                    }
+                    attempt += 1;
+                }
+                bits += 1;
+            }
+            found
+        };
+
+        const SLOT_COUNT: usize = if HASH.0 == 0 { 0 } else { 1 << HASH.1 };
+
+        const SLOTS: [$repr; SLOT_COUNT] = {
+            let mut slots = [VALUES[0]; SLOT_COUNT];
+            let mut i = 0;
+            while SLOT_COUNT > 0 && i < LEN {
+                slots[(hash_key(VALUES[i]).wrapping_mul(HASH.0) >> (64 - HASH.1)) as usize] = VALUES[i];
+                i += 1;
+            }
+            slots
+        };
+
+        // Only the selected table is built, since the others have a length of zero.
+        const SORTED_LEN: usize = if WORDS == 0 && SLOT_COUNT == 0 { LEN } else { 0 };
+
+        // Heapsort, since the enums that get here have thousands of variants.
+        const SORTED: [$repr; SORTED_LEN] = {
+            let mut values = [VALUES[0]; SORTED_LEN];
+            let mut i = 0;
+            while i < SORTED_LEN {
+                values[i] = VALUES[i];
+                i += 1;
+            }
+
+            // Builds a max-heap by sifting down from the middle, then repeatedly moves its root to
+            // the end of the shrinking heap.
+            let mut start = SORTED_LEN / 2;
+            let mut end = SORTED_LEN;
+            while end > 1 {
+                if start > 0 {
+                    start -= 1;
+                } else {
+                    end -= 1;
+                    let tmp = values[0];
+                    values[0] = values[end];
+                    values[end] = tmp;
+                }
+
+                let mut root = start;
+                loop {
+                    let mut child = 2 * root + 1;
+                    if child >= end {
+                        break;
+                    }
+                    if child + 1 < end && values[child] < values[child + 1] {
+                        child += 1;
+                    }
+                    if values[root] >= values[child] {
+                        break;
+                    }
+                    let tmp = values[root];
+                    values[root] = values[child];
+                    values[child] = tmp;
+                    root = child;
+                }
+            }
+            values
+        };
+
+        if WORDS > 0 {
+            $crate::DiscriminantSet::Bitmap {
+                min: MIN,
+                words: &BITMAP,
+            }
+        } else if SLOT_COUNT > 0 {
+            $crate::DiscriminantSet::Hashed {
+                multiplier: HASH.0,
+                shift: 64 - HASH.1,
+                slots: &SLOTS,
+            }
+        } else {
+            $crate::DiscriminantSet::Sorted(&SORTED)
+        }
+    }};
+}
@@ -0,0 +1,253 @@
+use crate::ascii::{parse_escaped, write_escaped, ParseAsciiError};
+use core::convert::TryFrom;
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::str::FromStr;
+
+/// A fixed-size byte code that's formatted as text, such as a [`FourCc`], for use as a
+/// [`VastEnum`](crate::VastEnum) repr.
+///
+/// `#[derive(VastEnum)]` uses this repr for enums whose variants are declared with byte-string
+/// literals, such as `#[vast_enum(value = b"fmt ")]`. Every literal must have the same length.
+///
+/// This struct has the same in-memory representation as `[u8; N]`.
+///
+/// With the `serde` feature, human-readable formats write the code as a string if its bytes are all
+/// printable ASCII, and as an array of bytes otherwise. Other formats always write bytes. Any of
+/// these forms is accepted when deserializing.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{FourCc, VastEnum};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// enum Chunk {
+///     #[vast_enum(value = b"fmt ")]
+///     Format,
+///     #[vast_enum(value = b"data")]
+///     Data,
+/// }
+///
+/// let chunk = VastChunk::from_int(FourCc::new(*b"fmt "));
+/// assert_eq!(chunk.variant(), Some(Chunk::Format));
+/// assert_eq!(format!("{:?}", chunk), "VastEnum('fmt ': Format)");
+///
+/// let chunk = VastChunk::from_int(FourCc::from_be_u32(0x4C49_5354));
+/// assert_eq!(chunk.variant(), None);
+/// assert_eq!(chunk.to_string(), "LIST");
+///
+/// let tag = FourCc::new([b'I', b'D', b'3', 0]);
+/// assert_eq!(tag.to_string(), r"ID3\x00");
+/// assert_eq!(tag.to_string().parse(), Ok(tag));
+///
+/// # #[cfg(feature = "bytemuck")]
+/// # {
+/// let chunk: &VastChunk = bytemuck::from_bytes(b"data");
+/// assert_eq!(chunk.variant(), Some(Chunk::Data));
+/// # }
+/// # }
+/// ```
+#[repr(transparent)]
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable,
+        zerocopy::Unaligned
+    )
+)]
+pub struct ByteTag<const N: usize>([u8; N]);
+
+/// A four-character code, as used by RIFF chunks, MP4 boxes, PNG chunks and TrueType tables.
+pub type FourCc = ByteTag<4>;
+
+impl<const N: usize> ByteTag<N> {
+    /// Creates a [`ByteTag`] from its bytes.
+    pub const fn new(bytes: [u8; N]) -> Self {
+        ByteTag(bytes)
+    }
+
+    /// Returns the bytes.
+    pub const fn bytes(self) -> [u8; N] {
+        self.0
+    }
+
+    /// Returns a reference to the bytes.
+    pub const fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Returns the bytes as a string, if they're all ASCII.
+    pub fn as_str(&self) -> Option<&str> {
+        if self.0.is_ascii() {
+            core::str::from_utf8(&self.0).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl FourCc {
+    /// Creates a [`FourCc`] from an integer whose big-endian bytes are the code, as in MP4 and PNG.
+    pub const fn from_be_u32(int: u32) -> Self {
+        ByteTag(int.to_be_bytes())
+    }
+
+    /// Returns the code as a big-endian integer.
+    pub const fn to_be_u32(self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+
+    /// Creates a [`FourCc`] from an integer whose little-endian bytes are the code, as in RIFF and
+    /// Windows `FOURCC` values.
+    pub const fn from_le_u32(int: u32) -> Self {
+        ByteTag(int.to_le_bytes())
+    }
+
+    /// Returns the code as a little-endian integer.
+    pub const fn to_le_u32(self) -> u32 {
+        u32::from_le_bytes(self.0)
+    }
+}
+
+impl<const N: usize> Default for ByteTag<N> {
+    fn default() -> Self {
+        ByteTag([0; N])
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for ByteTag<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        ByteTag(bytes)
+    }
+}
+
+impl<const N: usize> From<ByteTag<N>> for [u8; N] {
+    fn from(tag: ByteTag<N>) -> Self {
+        tag.0
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for ByteTag<N> {
+    type Error = core::array::TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; N]>::try_from(bytes).map(ByteTag)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ByteTag<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Formats the bytes like a character literal, such as `'fmt '`, with escapes for non-printable
+/// and non-ASCII bytes.
+impl<const N: usize> Debug for ByteTag<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_char('\'')?;
+        for &byte in &self.0 {
+            write_escaped(f, byte, false)?;
+        }
+
+        f.write_char('\'')
+    }
+}
+
+/// Formats the bytes as text, with escapes for backslashes and non-printable and non-ASCII bytes.
+impl<const N: usize> Display for ByteTag<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for &byte in &self.0 {
+            write_escaped(f, byte, true)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the bytes in the format of the `Display` impl, such as `fmt ` or `ID3\x00`.
+impl<const N: usize> FromStr for ByteTag<N> {
+    type Err = ParseAsciiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0; N];
+        parse_escaped(s, &mut bytes)?;
+        Ok(ByteTag(bytes))
+    }
+}
+
+// SAFETY: `ByteTag` is `repr(transparent)` over `[u8; N]`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<const N: usize> bytemuck::Zeroable for ByteTag<N> {}
+
+// SAFETY: `ByteTag` is `repr(transparent)` over `[u8; N]`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<const N: usize> bytemuck::Pod for ByteTag<N> {}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for ByteTag<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.0);
+        }
+
+        match self.as_str() {
+            Some(s) if self.0.iter().all(|&byte| (b' '..=b'~').contains(&byte)) => {
+                serializer.serialize_str(s)
+            }
+            _ => serializer.collect_seq(&self.0),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for ByteTag<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TagVisitor)
+        } else {
+            deserializer.deserialize_bytes(TagVisitor)
+        }
+    }
+}
+
+/// A visitor that accepts a [`ByteTag`] as a string, bytes or a sequence of bytes.
+#[cfg(feature = "serde")]
+struct TagVisitor<const N: usize>;
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::de::Visitor<'de> for TagVisitor<N> {
+    type Value = ByteTag<N>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "a string or bytes of length {}", N)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        ByteTag::try_from(v.as_bytes()).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        ByteTag::try_from(v).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(serde::de::Error::invalid_length(N + 1, &self));
+        }
+
+        Ok(ByteTag(bytes))
+    }
+}
@@ -0,0 +1,335 @@
+use crate::{EnumRepr, VastEnum, VastRepr};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use core::fmt::{Debug, Display, Formatter};
+use derivative::Derivative;
+
+/// The integer discriminant type of a [`TaggedUnion`]'s tag.
+type TagRepr<Union> = <<Union as TaggedUnion>::Tag as VastRepr>::Repr;
+
+/// A [`TaggedUnion`] value, or the raw payload of a tag that isn't known.
+///
+/// Decoding keeps the payload of an unknown tag as bytes, borrowed from the input when possible,
+/// and encoding writes them back unchanged, so data written by a newer version of a format survives
+/// a decode and re-encode.
+///
+/// # Example
+///
+/// A decoder for type-length-value records, each of which has a one-byte tag and a one-byte length:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{InvalidPayload, VastEnum, VastUnion};
+///
+/// #[derive(Debug, Clone, Eq, PartialEq, VastUnion)]
+/// #[repr(u8)]
+/// enum Attr {
+///     End = 0,
+///     Hostname(String) = 12,
+///     LeaseTime(u32) = 51,
+/// }
+///
+/// fn decode(mut input: &[u8]) -> Result<Vec<VastAttr<'_>>, InvalidPayload<u8>> {
+///     let mut attrs = Vec::new();
+///     while let [tag, len, rest @ ..] = input {
+///         let (payload, rest) = rest.split_at(usize::from(*len));
+///         attrs.push(VastUnion::decode(VastEnum::from_int(*tag), payload)?);
+///         input = rest;
+///     }
+///
+///     Ok(attrs)
+/// }
+///
+/// fn encode(attrs: &[VastAttr<'_>]) -> Vec<u8> {
+///     let mut output = Vec::new();
+///     for attr in attrs {
+///         let mut payload = Vec::new();
+///         attr.encode_payload(&mut payload);
+///         output.push(attr.tag().int());
+///         output.push(payload.len() as u8);
+///         output.extend(payload);
+///     }
+///
+///     output
+/// }
+///
+/// let input = [12, 2, b'p', b'c', 99, 3, 1, 2, 3, 51, 4, 0, 0, 0x0E, 0x10, 0, 0];
+/// let attrs = decode(&input).unwrap();
+/// assert_eq!(attrs[0].known(), Some(&Attr::Hostname("pc".to_string())));
+/// assert_eq!(attrs[1].tag().int(), 99);
+/// assert_eq!(attrs[1].unknown_payload(), Some(&[1, 2, 3][..]));
+/// assert_eq!(attrs[2].known(), Some(&Attr::LeaseTime(3600)));
+/// assert_eq!(attrs[3].known(), Some(&Attr::End));
+/// assert_eq!(encode(&attrs), input);
+///
+/// let error = decode(&[51, 2, 0, 0]).unwrap_err();
+/// assert_eq!(*error.tag(), 51);
+/// # }
+/// ```
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "Union: Debug, Union::Tag: Debug, TagRepr<Union>: Debug"),
+    Clone(bound = "Union: Clone"),
+    PartialEq(bound = "Union: PartialEq, Union::Tag: PartialEq"),
+    Eq(bound = "Union: Eq, Union::Tag: Eq")
+)]
+pub enum VastUnion<'a, Union: TaggedUnion> {
+    /// A value with a known tag, whose payload was decoded.
+    Known(Union),
+    /// A tag that isn't known, with its raw payload.
+    Unknown {
+        /// The tag, which normally isn't a valid discriminant of the tag enum.
+        tag: VastEnum<Union::Tag>,
+        /// The payload, exactly as it was decoded.
+        payload: Cow<'a, [u8]>,
+    },
+}
+
+impl<'a, Union: TaggedUnion> VastUnion<'a, Union> {
+    /// Decodes the payload of a known tag, or keeps the payload of an unknown tag as-is.
+    ///
+    /// Fails if the tag is known but its payload can't be decoded.
+    pub fn decode(
+        tag: VastEnum<Union::Tag>,
+        payload: &'a [u8],
+    ) -> Result<Self, InvalidPayload<TagRepr<Union>>> {
+        match tag.variant() {
+            Some(variant) => Union::decode_payload(variant, payload)
+                .map(VastUnion::Known)
+                .ok_or_else(|| InvalidPayload::new::<Union>(tag.int())),
+            None => Ok(VastUnion::Unknown {
+                tag,
+                payload: Cow::Borrowed(payload),
+            }),
+        }
+    }
+
+    /// Returns the tag.
+    pub fn tag(&self) -> VastEnum<Union::Tag> {
+        match self {
+            VastUnion::Known(union) => VastEnum::from_variant(union.tag()),
+            VastUnion::Unknown { tag, .. } => *tag,
+        }
+    }
+
+    /// Returns whether the tag is known.
+    pub fn is_known(&self) -> bool {
+        matches!(self, VastUnion::Known(_))
+    }
+
+    /// Returns a reference to the decoded value, if the tag is known.
+    pub fn known(&self) -> Option<&Union> {
+        match self {
+            VastUnion::Known(union) => Some(union),
+            VastUnion::Unknown { .. } => None,
+        }
+    }
+
+    /// Returns the decoded value, if the tag is known.
+    pub fn into_known(self) -> Option<Union> {
+        match self {
+            VastUnion::Known(union) => Some(union),
+            VastUnion::Unknown { .. } => None,
+        }
+    }
+
+    /// Returns the raw payload, if the tag isn't known.
+    pub fn unknown_payload(&self) -> Option<&[u8]> {
+        match self {
+            VastUnion::Known(_) => None,
+            VastUnion::Unknown { payload, .. } => Some(payload),
+        }
+    }
+
+    /// Appends the payload to `output`. The payload of an unknown tag is written exactly as it was
+    /// decoded.
+    pub fn encode_payload(&self, output: &mut Vec<u8>) {
+        match self {
+            VastUnion::Known(union) => union.encode_payload(output),
+            VastUnion::Unknown { payload, .. } => output.extend_from_slice(payload),
+        }
+    }
+
+    /// Converts into a [`VastUnion`] that owns its raw payload, if any.
+    pub fn into_owned(self) -> VastUnion<'static, Union> {
+        match self {
+            VastUnion::Known(union) => VastUnion::Known(union),
+            VastUnion::Unknown { tag, payload } => VastUnion::Unknown {
+                tag,
+                payload: Cow::Owned(payload.into_owned()),
+            },
+        }
+    }
+}
+
+impl<Union: TaggedUnion> From<Union> for VastUnion<'_, Union> {
+    fn from(union: Union) -> Self {
+        VastUnion::Known(union)
+    }
+}
+
+/// An enum with data, whose variants are identified by the variants of a fieldless tag enum.
+///
+/// This is implemented by `#[derive(VastUnion)]`, which also generates the tag enum. For an enum
+/// `Attr`, the tag enum is `AttrTag`, with the same variants, `#[repr(..)]` and discriminants. It
+/// derives `VastEnum`, so the tag's variants can also be declared with `#[vast_enum(value = ..)]`
+/// literals instead of discriminants. The derive also adds a `VastAttr<'a>` type alias
+/// for `VastUnion<'a, Attr>`.
+///
+/// Every variant must either be a unit variant, whose payload is empty, or have a single unnamed
+/// field that implements [`Payload`]:
+///
+/// ```compile_fail
+/// use vast_enum::VastUnion;
+///
+/// #[derive(VastUnion)]
+/// #[repr(u8)]
+/// enum Attr {
+///     Lease { seconds: u32 } = 51,
+/// }
+/// ```
+pub trait TaggedUnion: Sized {
+    /// The fieldless enum of tags.
+    type Tag: VastRepr + Into<<Self::Tag as VastRepr>::Repr>;
+
+    /// Returns the tag of the variant.
+    fn tag(&self) -> Self::Tag;
+
+    /// Decodes the payload of a variant with the given tag, if it's valid.
+    fn decode_payload(tag: Self::Tag, payload: &[u8]) -> Option<Self>;
+
+    /// Appends the payload of the variant to `output`.
+    fn encode_payload(&self, output: &mut Vec<u8>);
+}
+
+/// A value that can be the payload of a [`TaggedUnion`] variant.
+///
+/// Payloads are decoded from exactly the bytes of the payload, so any trailing bytes are an error.
+/// Integers are in big-endian byte order, as in most type-length-value formats.
+pub trait Payload: Sized {
+    /// Decodes the payload from all of `bytes`, if they're valid.
+    fn decode(bytes: &[u8]) -> Option<Self>;
+
+    /// Appends the payload to `output`.
+    fn encode(&self, output: &mut Vec<u8>);
+}
+
+impl Payload for () {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn encode(&self, _output: &mut Vec<u8>) {}
+}
+
+impl Payload for Vec<u8> {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        Some(bytes.to_vec())
+    }
+
+    fn encode(&self, output: &mut Vec<u8>) {
+        output.extend_from_slice(self)
+    }
+}
+
+/// Must be valid UTF-8.
+impl Payload for String {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        core::str::from_utf8(bytes).ok().map(String::from)
+    }
+
+    fn encode(&self, output: &mut Vec<u8>) {
+        output.extend_from_slice(self.as_bytes())
+    }
+}
+
+impl<const N: usize> Payload for [u8; N] {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok()
+    }
+
+    fn encode(&self, output: &mut Vec<u8>) {
+        output.extend_from_slice(self)
+    }
+}
+
+/// Uses the payload encoding of `Repr`, so unknown discriminants are kept.
+impl<Enum, Repr> Payload for VastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + Payload,
+{
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        Repr::decode(bytes).map(VastEnum::from_int)
+    }
+
+    fn encode(&self, output: &mut Vec<u8>) {
+        self.int().encode(output)
+    }
+}
+
+macro_rules! impl_payload {
+    ($($int:ty),*) => {
+        $(
+            impl Payload for $int {
+                fn decode(bytes: &[u8]) -> Option<Self> {
+                    bytes.try_into().ok().map(<$int>::from_be_bytes)
+                }
+
+                fn encode(&self, output: &mut Vec<u8>) {
+                    output.extend_from_slice(&self.to_be_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_payload!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// The error returned when the payload of a known tag can't be decoded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct InvalidPayload<Repr> {
+    tag: Repr,
+    union_name: &'static str,
+}
+
+impl<Repr> InvalidPayload<Repr> {
+    /// Creates an [`InvalidPayload`] for a payload with the given tag, which isn't valid for
+    /// `Union`.
+    pub fn new<Union>(tag: Repr) -> Self {
+        InvalidPayload {
+            tag,
+            union_name: core::any::type_name::<Union>(),
+        }
+    }
+
+    /// Returns the integer discriminant of the tag.
+    pub fn tag(&self) -> &Repr {
+        &self.tag
+    }
+
+    /// Returns the name of the union type, as given by [`core::any::type_name`].
+    pub fn union_name(&self) -> &'static str {
+        self.union_name
+    }
+}
+
+impl<Repr: Display> Display for InvalidPayload<Repr> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid payload for tag {} of union `{}`",
+            self.tag, self.union_name
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Repr: Debug + Display> std::error::Error for InvalidPayload<Repr> {}
@@ -0,0 +1,135 @@
+use crate::{EnumRepr, VastEnum, VastRepr};
+
+/// A [`VastEnum`] split into either a known variant or an unknown integer discriminant.
+///
+/// This makes it possible to `match` on every known variant exhaustively while still handling
+/// unknown values, either directly or with [`vast_match!`](crate::vast_match).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{VastEnum, VastVariant};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum Color {
+///     Red = 0,
+///     Yellow = 1,
+///     Green = 2,
+/// }
+///
+/// let variant = VastVariant::from(VastColor::from_int(9));
+/// assert_eq!(variant, VastVariant::Unknown(9));
+///
+/// let enum_ = VastColor::from(VastVariant::Known(Color::Red));
+/// assert_eq!(enum_.int(), 0);
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum VastVariant<Enum, Repr = <Enum as VastRepr>::Repr> {
+    /// A valid discriminant, converted to the enum type.
+    Known(Enum),
+    /// A discriminant that isn't a valid value for the enum type.
+    Unknown(Repr),
+}
+
+impl<Enum, Repr> From<VastEnum<Enum, Repr>> for VastVariant<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    fn from(enum_: VastEnum<Enum, Repr>) -> Self {
+        match enum_.variant() {
+            Some(variant) => VastVariant::Known(variant),
+            None => VastVariant::Unknown(enum_.int()),
+        }
+    }
+}
+
+impl<Enum, Repr> From<VastVariant<Enum, Repr>> for VastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    fn from(variant: VastVariant<Enum, Repr>) -> Self {
+        match variant {
+            VastVariant::Known(variant) => VastEnum::from_variant(variant),
+            VastVariant::Unknown(int) => VastEnum::from_int(int),
+        }
+    }
+}
+
+/// Matches on a [`VastEnum`], requiring an arm for every known variant plus an `unknown(..)` arm.
+///
+/// The known arms are checked for exhaustiveness like a regular `match` on the enum. The `unknown`
+/// arm comes last, and binds the integer discriminant of values that aren't a known variant. Arms
+/// are separated by commas.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{vast_match, VastEnum};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u8)]
+/// enum Color {
+///     Red = 0,
+///     Yellow = 1,
+///     Green = 2,
+/// }
+///
+/// fn describe(color: VastColor) -> String {
+///     vast_match!(color, {
+///         Color::Red | Color::Yellow => "warm".to_owned(),
+///         Color::Green => "cool".to_owned(),
+///         unknown(n) => format!("unknown ({})", n),
+///     })
+/// }
+///
+/// assert_eq!(describe(VastEnum::from_variant(Color::Yellow)), "warm");
+/// assert_eq!(describe(VastEnum::from_int(7)), "unknown (7)");
+/// # }
+/// ```
+///
+/// Leaving out a known variant is a compile error:
+///
+/// ```compile_fail
+/// # use vast_enum::{vast_match, VastEnum};
+/// #
+/// # #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// # #[repr(u8)]
+/// # enum Color {
+/// #     Red = 0,
+/// #     Yellow = 1,
+/// #     Green = 2,
+/// # }
+/// #
+/// let color = VastColor::from_int(1);
+/// let warm = vast_match!(color, {
+///     Color::Red | Color::Yellow => true,
+///     unknown(_) => false,
+/// });
+/// ```
+#[macro_export]
+macro_rules! vast_match {
+    ($value:expr, { $($arms:tt)* }) => {
+        $crate::vast_match!(@arms $value, [] $($arms)*)
+    };
+    (@arms $value:expr, [$($known:tt)*] unknown($unknown:pat) => $body:expr $(,)?) => {
+        match $crate::VastVariant::from($value) {
+            $($known)*
+            $crate::VastVariant::Unknown($unknown) => $body,
+        }
+    };
+    (@arms $value:expr, [$($known:tt)*] $($pat:pat)|+ => $body:expr, $($rest:tt)*) => {
+        $crate::vast_match!(
+            @arms $value,
+            [$($known)* $($crate::VastVariant::Known($pat))|+ => $body,]
+            $($rest)*
+        )
+    };
+}
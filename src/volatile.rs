@@ -0,0 +1,152 @@
+use crate::{EnumRepr, FlagsRepr, VastEnum, VastRepr};
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::ptr;
+
+/// A [`VastEnum`] that's only accessed through volatile reads and writes, for memory-mapped
+/// hardware registers.
+///
+/// This struct has the same in-memory representation as `Repr`, so a `#[repr(C)]` struct of them
+/// can describe a register block. Hardware may report reserved values, which are read back as
+/// unknown discriminants instead of causing undefined behavior.
+///
+/// Registers that hold other fields next to the enum can be accessed with
+/// [`read_masked`](VolatileVastEnum::read_masked) and
+/// [`write_masked`](VolatileVastEnum::write_masked), as long as the enum's discriminants are
+/// written in place, with the field's bits already shifted.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use vast_enum::{VastEnum, VolatileVastEnum};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+/// #[repr(u32)]
+/// enum Mode {
+///     Off = 0b00 << 4,
+///     Low = 0b01 << 4,
+///     High = 0b10 << 4,
+/// }
+///
+/// const MODE_MASK: u32 = 0b11 << 4;
+///
+/// #[repr(C)]
+/// struct Registers {
+///     control: VolatileVastEnum<Mode>,
+///     status: VolatileVastEnum<Mode>,
+/// }
+///
+/// // Real code would get this from the hardware's address, for example with
+/// // `unsafe { &*(0x4000_0000 as *const Registers) }`.
+/// let registers = Registers {
+///     control: VolatileVastEnum::new(VastEnum::from_int(0b1_01_0001)),
+///     status: VolatileVastEnum::from_variant(Mode::Off),
+/// };
+///
+/// assert_eq!(registers.control.read_masked(MODE_MASK).variant(), Some(Mode::Low));
+/// registers.control.write_masked(VastEnum::from_variant(Mode::High), MODE_MASK);
+/// assert_eq!(registers.control.read().int(), 0b1_10_0001);
+///
+/// registers.status.write(VastEnum::from_int(0b11 << 4));
+/// assert_eq!(registers.status.read().variant(), None);
+/// # }
+/// ```
+#[repr(transparent)]
+pub struct VolatileVastEnum<Enum, Repr = <Enum as VastRepr>::Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    value: UnsafeCell<Repr>,
+    phantom: PhantomData<Enum>,
+}
+
+impl<Enum, Repr> VolatileVastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    /// Creates a [`VolatileVastEnum`] holding `value`.
+    pub const fn new(value: VastEnum<Enum, Repr>) -> Self {
+        VolatileVastEnum {
+            value: UnsafeCell::new(value.int()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates a [`VolatileVastEnum`] holding an enum variant.
+    pub fn from_variant(variant: Enum) -> Self {
+        Self::new(VastEnum::from_variant(variant))
+    }
+
+    /// Views a register at `address` as a [`VolatileVastEnum`].
+    ///
+    /// # Safety
+    ///
+    /// `address` must be non-null and aligned for `Repr`, and must stay valid for volatile reads
+    /// and writes for the lifetime `'a`.
+    pub unsafe fn from_ptr<'a>(address: *mut Repr) -> &'a Self {
+        // SAFETY: `VolatileVastEnum` is `repr(transparent)` over `UnsafeCell<Repr>`, which has the
+        // same layout as `Repr`, and the caller guarantees the pointer is valid.
+        &*(address as *const Self)
+    }
+
+    /// Returns a pointer to the register.
+    pub fn as_ptr(&self) -> *mut Repr {
+        self.value.get()
+    }
+
+    /// Reads the register with a volatile read.
+    pub fn read(&self) -> VastEnum<Enum, Repr> {
+        // SAFETY: the pointer comes from a reference, so it's valid and aligned.
+        VastEnum::from_int(unsafe { ptr::read_volatile(self.value.get()) })
+    }
+
+    /// Writes `value` to the register with a volatile write.
+    pub fn write(&self, value: VastEnum<Enum, Repr>) {
+        // SAFETY: the pointer comes from a reference, so it's valid and aligned.
+        unsafe { ptr::write_volatile(self.value.get(), value.int()) }
+    }
+
+    /// Writes an enum variant to the register with a volatile write.
+    pub fn write_variant(&self, variant: Enum) {
+        self.write(VastEnum::from_variant(variant))
+    }
+
+    /// Reads the register, transforms the value with `f`, and writes the result back.
+    ///
+    /// The read and the write are separate volatile accesses, so this isn't atomic.
+    pub fn modify(&self, f: impl FnOnce(VastEnum<Enum, Repr>) -> VastEnum<Enum, Repr>) {
+        self.write(f(self.read()))
+    }
+}
+
+impl<Enum, Repr> VolatileVastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum> + FlagsRepr,
+{
+    /// Reads the register and keeps only the bits in `mask`.
+    pub fn read_masked(&self, mask: Repr) -> VastEnum<Enum, Repr> {
+        VastEnum::from_int(self.read().int() & mask)
+    }
+
+    /// Replaces the bits in `mask` with those of `value`, keeping the register's other bits.
+    ///
+    /// This reads the register and then writes it, as separate volatile accesses.
+    pub fn write_masked(&self, value: VastEnum<Enum, Repr>, mask: Repr) {
+        self.modify(|old| VastEnum::from_int((old.int() & !mask) | (value.int() & mask)))
+    }
+}
+
+impl<Enum, Repr> From<VastEnum<Enum, Repr>> for VolatileVastEnum<Enum, Repr>
+where
+    Enum: Into<Repr>,
+    Repr: EnumRepr<Enum>,
+{
+    fn from(value: VastEnum<Enum, Repr>) -> Self {
+        Self::new(value)
+    }
+}
@@ -0,0 +1,132 @@
+//! Checks the code generated by `#[derive(VastEnum)]`.
+
+#![forbid(unsafe_code)]
+
+use std::convert::TryFrom;
+use vast_enum::VastEnum;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+#[repr(i16)]
+enum Sparse {
+    Low = -300,
+    Zero = 0,
+    High = 3000,
+}
+
+#[test]
+fn try_from_repr() {
+    assert_eq!(Sparse::try_from(-300), Ok(Sparse::Low));
+    assert_eq!(Sparse::try_from(0), Ok(Sparse::Zero));
+    assert_eq!(Sparse::try_from(3000), Ok(Sparse::High));
+    assert_eq!(Sparse::try_from(1).unwrap_err().into_value(), 1);
+    assert_eq!(VastSparse::HIGH.variant(), Some(Sparse::High));
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+#[repr(u32)]
+#[vast_enum(repr = std::num::NonZeroU32)]
+enum Codec {
+    Opus = 1,
+    Vorbis = 7,
+}
+
+#[test]
+fn non_zero_consts() {
+    assert_eq!(VastCodec::OPUS.int().get(), 1);
+    assert_eq!(VastCodec::VORBIS.variant(), Some(Codec::Vorbis));
+}
+
+type Nz = std::num::NonZeroU16;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+#[repr(u16)]
+#[vast_enum(repr = Nz)]
+enum Port {
+    Http = 80,
+    Https = 443,
+}
+
+#[test]
+fn aliased_narrow_consts() {
+    assert_eq!(VastPort::HTTPS.int().get(), 443);
+    assert_eq!(VastPort::HTTP.variant(), Some(Port::Http));
+}
+
+#[cfg(feature = "arbitrary-int")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+#[repr(u8)]
+#[vast_enum(repr = arbitrary_int::u3)]
+enum Priority {
+    Low = 0,
+    High = 7,
+}
+
+#[cfg(feature = "arbitrary-int")]
+#[test]
+fn arbitrary_int_consts() {
+    assert_eq!(VastPriority::HIGH.int(), arbitrary_int::u3::new(7));
+    assert_eq!(VastPriority::LOW.variant(), Some(Priority::Low));
+}
+
+#[cfg(feature = "alloc")]
+mod union {
+    use std::convert::TryFrom;
+    use vast_enum::{FourCc, VastEnum, VastUnion};
+
+    #[derive(Debug, Clone, Eq, PartialEq, VastUnion)]
+    enum Chunk {
+        #[vast_enum(value = b"fmt ")]
+        Format(u32),
+        #[vast_enum(value = b"data")]
+        Data(Vec<u8>),
+        #[vast_enum(value = b"end ")]
+        End,
+    }
+
+    fn decode<'a>(tag: &[u8; 4], payload: &'a [u8]) -> VastChunk<'a> {
+        VastUnion::decode(VastEnum::from_int(FourCc::new(*tag)), payload).unwrap()
+    }
+
+    #[test]
+    fn literal_tags() {
+        assert_eq!(
+            ChunkTag::try_from(FourCc::new(*b"fmt ")),
+            Ok(ChunkTag::Format)
+        );
+
+        let chunk = decode(b"fmt ", &[0, 0, 0, 7]);
+        assert_eq!(chunk.known(), Some(&Chunk::Format(7)));
+        assert_eq!(chunk.tag().int(), FourCc::new(*b"fmt "));
+
+        let chunk = decode(b"LIST", &[1, 2, 3]);
+        assert_eq!(chunk.tag().int(), FourCc::new(*b"LIST"));
+        assert_eq!(chunk.unknown_payload(), Some(&[1, 2, 3][..]));
+
+        let mut payload = Vec::new();
+        chunk.encode_payload(&mut payload);
+        assert_eq!(payload, [1, 2, 3]);
+
+        let chunk = VastChunk::from(Chunk::Data(vec![4, 5]));
+        assert_eq!(chunk.tag().variant(), Some(ChunkTag::Data));
+        let mut payload = Vec::new();
+        chunk.encode_payload(&mut payload);
+        assert_eq!(payload, [4, 5]);
+    }
+
+    #[test]
+    fn invalid_payload() {
+        let tag = VastEnum::from_int(FourCc::new(*b"fmt "));
+        let error = VastChunk::decode(tag, &[0, 7]).unwrap_err();
+        assert_eq!(*error.tag(), FourCc::new(*b"fmt "));
+        assert!(error
+            .to_string()
+            .starts_with("invalid payload for tag fmt  of union"));
+
+        let tag = VastEnum::from_int(FourCc::new(*b"end "));
+        assert!(VastChunk::decode(tag, &[0]).is_err());
+        assert_eq!(
+            VastChunk::decode(tag, &[]).unwrap().known(),
+            Some(&Chunk::End)
+        );
+    }
+}
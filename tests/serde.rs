@@ -0,0 +1,483 @@
+//! Checks the serde representations against several formats.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use vast_enum::{AsciiByte, FourCc, VastEnum, VastFlags};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+#[repr(u16)]
+enum Color {
+    Red = 0,
+    Yellow = 1,
+    Green = 2,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Raw {
+    id: u32,
+    color: u16,
+    flags: u16,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Wrapped {
+    id: u32,
+    color: VastColor,
+    flags: VastFlags<Color>,
+}
+
+/// Checks that a `VastEnum` field serializes exactly like the raw integer field it replaces.
+///
+/// Encodes pairs of equivalent `Raw` and `Wrapped` values, and checks that both encode to the same
+/// data and that each decodes from the other's encoding.
+fn check<Encoded: Debug + Eq>(
+    encode_raw: impl Fn(&Raw) -> Encoded,
+    encode_wrapped: impl Fn(&Wrapped) -> Encoded,
+    decode_raw: impl Fn(&Encoded) -> Raw,
+    decode_wrapped: impl Fn(&Encoded) -> Wrapped,
+) {
+    for &int in &[0, 2, 5, 0xFFFF] {
+        let raw = Raw {
+            id: 7,
+            color: int,
+            flags: int,
+        };
+        let wrapped = Wrapped {
+            id: 7,
+            color: VastEnum::from_int(int),
+            flags: VastFlags::from_bits(int),
+        };
+
+        let raw_encoded = encode_raw(&raw);
+        let wrapped_encoded = encode_wrapped(&wrapped);
+        assert_eq!(raw_encoded, wrapped_encoded);
+        assert_eq!(decode_wrapped(&raw_encoded), wrapped);
+        assert_eq!(decode_raw(&wrapped_encoded), raw);
+    }
+}
+
+#[test]
+fn serde_json() {
+    check(
+        |v| serde_json::to_string(v).unwrap(),
+        |v| serde_json::to_string(v).unwrap(),
+        |s| serde_json::from_str(s).unwrap(),
+        |s| serde_json::from_str(s).unwrap(),
+    );
+
+    let color: VastColor = serde_json::from_str("5").unwrap();
+    assert_eq!(color.int(), 5);
+    assert_eq!(serde_json::to_string(&color).unwrap(), "5");
+    assert_eq!(
+        serde_json::to_string(&VastColor::from_variant(Color::Yellow)).unwrap(),
+        "1"
+    );
+}
+
+#[test]
+fn bincode() {
+    check(
+        |v| bincode::serialize(v).unwrap(),
+        |v| bincode::serialize(v).unwrap(),
+        |b| bincode::deserialize(b).unwrap(),
+        |b| bincode::deserialize(b).unwrap(),
+    );
+}
+
+#[test]
+fn postcard() {
+    check(
+        |v| postcard::to_stdvec(v).unwrap(),
+        |v| postcard::to_stdvec(v).unwrap(),
+        |b| postcard::from_bytes(b).unwrap(),
+        |b| postcard::from_bytes(b).unwrap(),
+    );
+}
+
+#[test]
+fn ciborium() {
+    fn to_vec<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    check(
+        to_vec,
+        to_vec,
+        |b| ciborium::from_reader(&b[..]).unwrap(),
+        |b| ciborium::from_reader(&b[..]).unwrap(),
+    );
+}
+
+#[test]
+fn out_of_range() {
+    assert!(serde_json::from_str::<VastColor>("65536").is_err());
+    assert!(serde_json::from_str::<VastColor>("-1").is_err());
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Counts {
+    #[serde(with = "vast_enum::serde::named_keys")]
+    counts: BTreeMap<VastColor, u32>,
+    #[serde(with = "vast_enum::serde::named_keys")]
+    hashed: HashMap<VastColor, u32>,
+}
+
+fn counts() -> Counts {
+    let mut counts = BTreeMap::new();
+    counts.insert(VastEnum::from_variant(Color::Red), 1);
+    counts.insert(VastEnum::from_int(300), 2);
+    let mut hashed = HashMap::new();
+    hashed.insert(VastEnum::from_variant(Color::Green), 3);
+
+    Counts { counts, hashed }
+}
+
+#[test]
+fn map_keys_json() {
+    let json = serde_json::to_string(&counts()).unwrap();
+    assert_eq!(json, r#"{"counts":{"Red":1,"300":2},"hashed":{"Green":3}}"#);
+    assert_eq!(serde_json::from_str::<Counts>(&json).unwrap(), counts());
+    assert_eq!(
+        serde_json::from_str::<Counts>(r#"{"counts":{"0":1,"300":2},"hashed":{"2":3}}"#).unwrap(),
+        counts()
+    );
+    assert!(serde_json::from_str::<Counts>(r#"{"counts":{"Blue":1},"hashed":{}}"#).is_err());
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+#[repr(u8)]
+enum Version {
+    #[vast_enum(rename = "2")]
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Versions {
+    #[serde(with = "vast_enum::serde::named")]
+    version: VastEnum<Version>,
+    #[serde(with = "vast_enum::serde::named_keys")]
+    counts: BTreeMap<VastEnum<Version>, u32>,
+}
+
+#[test]
+fn numeric_names() {
+    let mut counts = BTreeMap::new();
+    counts.insert(VastEnum::from_variant(Version::V1), 1);
+    let versions = Versions {
+        version: VastEnum::from_variant(Version::V1),
+        counts,
+    };
+    let json = serde_json::to_string(&versions).unwrap();
+    assert_eq!(json, r#"{"version":"2","counts":{"2":1}}"#);
+    assert_eq!(serde_json::from_str::<Versions>(&json).unwrap(), versions);
+
+    let versions: Versions = serde_json::from_str(r#"{"version":2,"counts":{}}"#).unwrap();
+    assert_eq!(versions.version.variant(), Some(Version::V2));
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+enum MsgType {
+    #[vast_enum(value = 'A')]
+    Logon,
+    #[vast_enum(value = '8')]
+    ExecutionReport,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Messages {
+    #[serde(with = "vast_enum::serde::named")]
+    known: VastMsgType,
+    #[serde(with = "vast_enum::serde::named")]
+    unknown: VastMsgType,
+    #[serde(with = "vast_enum::serde::named_keys")]
+    counts: BTreeMap<VastMsgType, u32>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+enum Side {
+    #[vast_enum(value = '1')]
+    B,
+    #[vast_enum(value = '2')]
+    S,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Order {
+    #[serde(with = "vast_enum::serde::named")]
+    side: VastSide,
+}
+
+#[test]
+fn named_char() {
+    let mut counts = BTreeMap::new();
+    counts.insert(VastEnum::from_variant(MsgType::ExecutionReport), 1);
+    counts.insert(VastEnum::from_int('Z'), 2);
+    let messages = Messages {
+        known: VastEnum::from_variant(MsgType::Logon),
+        unknown: VastEnum::from_int('Z'),
+        counts,
+    };
+
+    let json = serde_json::to_string(&messages).unwrap();
+    assert_eq!(
+        json,
+        r#"{"known":"Logon","unknown":"Z","counts":{"ExecutionReport":1,"Z":2}}"#
+    );
+    assert_eq!(serde_json::from_str::<Messages>(&json).unwrap(), messages);
+
+    let messages: Messages =
+        serde_json::from_str(r#"{"known":"8","unknown":"Z","counts":{}}"#).unwrap();
+    assert_eq!(messages.known.variant(), Some(MsgType::ExecutionReport));
+    assert!(
+        serde_json::from_str::<Messages>(r#"{"known":"ZZ","unknown":"Z","counts":{}}"#).is_err()
+    );
+
+    // An unknown 'B' is written with a prefix, since "B" is the name of `Side::B`.
+    for &(int, expected) in &[('B', r##"{"side":"#B"}"##), ('#', r##"{"side":"#"}"##)] {
+        let order = Order {
+            side: VastEnum::from_int(int),
+        };
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), order);
+    }
+    let order: Order = serde_json::from_str(r#"{"side":"B"}"#).unwrap();
+    assert_eq!(order.side.variant(), Some(Side::B));
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+enum ByteSide {
+    #[vast_enum(value = b'1')]
+    B,
+    #[vast_enum(value = b'2')]
+    S,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct ByteSides {
+    #[serde(with = "vast_enum::serde::named_keys")]
+    counts: BTreeMap<VastByteSide, u32>,
+}
+
+#[test]
+fn named_ascii_keys() {
+    let counts = [(b'1', 1), (b'B', 2), (b'Z', 3), (b'\\', 4), (0xFF, 5)]
+        .iter()
+        .map(|&(byte, count)| (VastEnum::from_int(AsciiByte::new(byte)), count))
+        .collect();
+    let sides = ByteSides { counts };
+
+    let json = serde_json::to_string(&sides).unwrap();
+    assert_eq!(
+        json,
+        r##"{"counts":{"B":1,"#B":2,"Z":3,"\\\\":4,"\\xff":5}}"##
+    );
+    assert_eq!(serde_json::from_str::<ByteSides>(&json).unwrap(), sides);
+
+    let toml = toml::to_string(&sides).unwrap();
+    assert_eq!(toml::from_str::<ByteSides>(&toml).unwrap(), sides);
+    assert!(serde_json::from_str::<ByteSides>(r#"{"counts":{"ZZ":1}}"#).is_err());
+}
+
+#[test]
+fn map_keys_toml() {
+    let toml = toml::to_string(&counts()).unwrap();
+    assert_eq!(toml::from_str::<Counts>(&toml).unwrap(), counts());
+    assert_eq!(
+        toml::from_str::<Counts>("[counts]\nRed = 1\n300 = 2\n\n[hashed]\n2 = 3\n").unwrap(),
+        counts()
+    );
+}
+
+#[test]
+fn map_keys_yaml() {
+    let yaml = serde_yaml::to_string(&counts()).unwrap();
+    assert_eq!(serde_yaml::from_str::<Counts>(&yaml).unwrap(), counts());
+    assert_eq!(
+        serde_yaml::from_str::<Counts>("counts:\n  0: 1\n  300: 2\nhashed:\n  Green: 3\n").unwrap(),
+        counts()
+    );
+}
+
+#[test]
+fn map_keys_binary() {
+    let bytes = bincode::serialize(&counts()).unwrap();
+    assert_eq!(bincode::deserialize::<Counts>(&bytes).unwrap(), counts());
+}
+
+#[cfg(feature = "alloc")]
+mod str_enum {
+    use serde::{Deserialize, Serialize};
+    use vast_enum::{VastEnum, VastStrEnum};
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+    #[repr(u8)]
+    enum Method {
+        #[vast_enum(rename = "GET")]
+        Get,
+        #[vast_enum(rename = "POST")]
+        Post,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    struct Request<'a> {
+        #[serde(borrow, with = "vast_enum::serde::borrowed")]
+        method: VastStrEnum<'a, Method>,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    struct OwnedRequest {
+        method: VastStrEnum<'static, Method>,
+    }
+
+    /// Checks whether `value` points into `input`, rather than being a copy of it.
+    fn borrows(value: &str, input: &[u8]) -> bool {
+        input.as_ptr_range().contains(&value.as_ptr())
+    }
+
+    #[test]
+    fn json() {
+        let json = r#"{"method":"POST"}"#;
+        let request: Request = serde_json::from_str(json).unwrap();
+        assert_eq!(request.method.variant(), Some(Method::Post));
+        assert_eq!(serde_json::to_string(&request).unwrap(), json);
+
+        let json = r#"{"method":"BREW"}"#;
+        let request: Request = serde_json::from_str(json).unwrap();
+        assert!(borrows(request.method.unknown().unwrap(), json.as_bytes()));
+        assert_eq!(serde_json::to_string(&request).unwrap(), json);
+
+        // Escapes can't be borrowed, so the string is copied.
+        let request: Request = serde_json::from_str(r#"{"method":"BR\u0045W"}"#).unwrap();
+        assert_eq!(request.method.unknown(), Some("BREW"));
+
+        let request: Request = serde_json::from_str(r#"{"method":"get"}"#).unwrap();
+        assert_eq!(request.method.unknown(), Some("get"));
+    }
+
+    #[test]
+    fn binary() {
+        for method in &["GET", "PATCH"] {
+            let request = Request {
+                method: VastStrEnum::new(*method),
+            };
+            let bytes = postcard::to_allocvec(&request).unwrap();
+            let decoded: Request = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, request);
+            if let Some(unknown) = decoded.method.unknown() {
+                assert!(borrows(unknown, &bytes));
+            }
+        }
+    }
+
+    #[test]
+    fn owned() {
+        let json = br#"{"method":"BREW"}"#;
+        let request: OwnedRequest = serde_json::from_reader(&json[..]).unwrap();
+        assert_eq!(request.method.unknown(), Some("BREW"));
+
+        let request: OwnedRequest = serde_json::from_reader(&br#"{"method":"GET"}"#[..]).unwrap();
+        assert_eq!(request.method.variant(), Some(Method::Get));
+
+        let value = serde_json::json!({ "method": "PATCH" });
+        let request: OwnedRequest = serde_json::from_value(value).unwrap();
+        assert_eq!(request.method.unknown(), Some("PATCH"));
+
+        // The default representation copies even when the input could be borrowed.
+        let json = r#""BREW""#;
+        let method: VastStrEnum<Method> = serde_json::from_str(json).unwrap();
+        assert_eq!(method, VastStrEnum::new("BREW"));
+        assert!(!borrows(method.unknown().unwrap(), json.as_bytes()));
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, VastEnum)]
+enum Chunk {
+    #[vast_enum(value = b"fmt ")]
+    Format,
+    #[vast_enum(value = b"data")]
+    Data,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Riff {
+    chunks: Vec<VastChunk>,
+}
+
+#[test]
+fn byte_tags() {
+    let riff = Riff {
+        chunks: vec![
+            VastEnum::from_variant(Chunk::Format),
+            VastEnum::from_variant(Chunk::Data),
+            VastEnum::from_int(FourCc::new([b'L', 0, 0xFF, b'\n'])),
+        ],
+    };
+
+    let json = serde_json::to_string(&riff).unwrap();
+    assert_eq!(json, r#"{"chunks":["fmt ","data",[76,0,255,10]]}"#);
+    assert_eq!(serde_json::from_str::<Riff>(&json).unwrap(), riff);
+    assert!(serde_json::from_str::<Riff>(r#"{"chunks":["fmt"]}"#).is_err());
+    assert!(serde_json::from_str::<Riff>(r#"{"chunks":[[1,2,3,4,5]]}"#).is_err());
+
+    let yaml = serde_yaml::to_string(&riff).unwrap();
+    assert_eq!(serde_yaml::from_str::<Riff>(&yaml).unwrap(), riff);
+
+    let bytes = bincode::serialize(&riff).unwrap();
+    assert_eq!(bincode::deserialize::<Riff>(&bytes).unwrap(), riff);
+
+    let bytes = postcard::to_allocvec(&riff).unwrap();
+    assert_eq!(&bytes[..6], b"\x03\x04fmt ");
+    assert_eq!(postcard::from_bytes::<Riff>(&bytes).unwrap(), riff);
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&riff, &mut bytes).unwrap();
+    assert_eq!(ciborium::from_reader::<Riff, _>(&bytes[..]).unwrap(), riff);
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct ChunkSizes {
+    #[serde(with = "vast_enum::serde::named_keys")]
+    sizes: BTreeMap<VastChunk, u32>,
+    #[serde(with = "vast_enum::serde::named")]
+    last: VastChunk,
+}
+
+#[test]
+fn named_byte_tags() {
+    let sizes = [
+        (*b"fmt ", 1),
+        (*b"data", 2),
+        (*b"Data", 3),
+        ([0, 1, 2, b'\\'], 4),
+    ]
+    .iter()
+    .map(|&(bytes, size)| (VastEnum::from_int(FourCc::new(bytes)), size))
+    .collect();
+    let chunks = ChunkSizes {
+        sizes,
+        last: VastEnum::from_int(FourCc::new([0, 1, 2, 3])),
+    };
+
+    let json = serde_json::to_string(&chunks).unwrap();
+    assert_eq!(
+        json,
+        r##"{"sizes":{"\\x00\\x01\\x02\\\\":4,"#Data":3,"Data":2,"Format":1},"last":[0,1,2,3]}"##
+    );
+    assert_eq!(serde_json::from_str::<ChunkSizes>(&json).unwrap(), chunks);
+
+    let yaml = serde_yaml::to_string(&chunks).unwrap();
+    assert_eq!(serde_yaml::from_str::<ChunkSizes>(&yaml).unwrap(), chunks);
+
+    let chunks = ChunkSizes {
+        sizes: BTreeMap::new(),
+        last: VastEnum::from_int(FourCc::new(*b"Data")),
+    };
+    let json = serde_json::to_string(&chunks).unwrap();
+    assert_eq!(json, r##"{"sizes":{},"last":"#Data"}"##);
+    assert_eq!(serde_json::from_str::<ChunkSizes>(&json).unwrap(), chunks);
+}
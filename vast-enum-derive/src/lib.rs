@@ -0,0 +1,703 @@
+//! Derive macro for the [vast-enum][1] crate.
+//!
+//! [1]: https://crates.io/crates/vast-enum
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::{
+    parenthesized, parse_macro_input, token, Data, DeriveInput, Error, Fields, Ident, Lit, LitStr,
+    Type, Variant,
+};
+
+const INTEGER_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Implements the conversions `VastEnum` needs for a fieldless enum with a primitive integer
+/// `#[repr(..)]`.
+///
+/// For an enum `Color` declared with `#[repr(u8)]`, this generates:
+///
+/// - `From<Color> for u8` and `TryFrom<u8> for Color`,
+/// - `TryFrom<VastEnum<Color, u8>> for Color`,
+/// - `VastRepr for Color`, `Variants for Color` and `ValidityTable<u8> for Color`,
+/// - a type alias `VastColor = VastEnum<Color, u8>`, with the same visibility as the enum,
+/// - a `VastColorConsts` trait, implemented for `VastColor`, with a constant such as
+///   `VastColor::RED` for each variant.
+///
+/// Since the conversions go through the declared repr, a discriminant that doesn't fit in it is a
+/// compile error. `TryFrom<u8>` rejects invalid values with a lookup in the validity table, and
+/// then matches on the discriminants, which rustc compiles to a switch. Since that match can't
+/// fail, `VastEnum::is_valid` only does the lookup. The generated code is free of `unsafe`, so it
+/// can be used in crates with `#![forbid(unsafe_code)]`.
+///
+/// With `#[vast_enum(repr = NonZeroU8)]`, or any other type that implements `NarrowRepr` and
+/// `NarrowConst`, the declared repr is only used for storage. The conversions, `VastRepr`, the
+/// alias and the constants then use that type instead, and every discriminant must be in its range.
+///
+/// Variants can instead be declared with literals, such as `#[vast_enum(value = 'A')]`, in which
+/// case the enum doesn't need a `#[repr(..)]`. The repr is `char` for character literals,
+/// `AsciiByte` for byte literals, which must be ASCII, and `ByteTag<N>` for byte-string literals of
+/// length `N`. Every variant needs a literal of the same kind, and the conversions match on the
+/// literals.
+///
+/// A variant's name in `Variants::NAMES` defaults to its identifier, and can be changed with
+/// `#[vast_enum(rename = "GET")]`. Names must be unique.
+#[proc_macro_derive(VastEnum, attributes(vast_enum))]
+pub fn derive_vast_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `TaggedUnion` for an enum with data, so it can be wrapped in a `VastUnion`. This
+/// needs the `alloc` feature of vast-enum.
+///
+/// For an enum `Attr`, this generates:
+///
+/// - a fieldless `AttrTag` enum with the same variants, discriminants, `#[repr(..)]` and
+///   `#[vast_enum(..)]` attributes, which derives `VastEnum`, `Debug`, `Copy`, `Clone`, `Eq`,
+///   `PartialEq` and `Hash`,
+/// - `TaggedUnion for Attr`, with `AttrTag` as its tag,
+/// - a type alias `VastAttr<'a> = VastUnion<'a, Attr>`, with the same visibility as the enum.
+///
+/// Every variant must be either a unit variant, whose payload is empty, or a variant with a single
+/// unnamed field, whose type implements `Payload`.
+#[proc_macro_derive(VastUnion, attributes(vast_enum))]
+pub fn derive_vast_union(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_union(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The conversions between an enum and its repr, generated differently depending on how the
+/// variants are declared.
+struct Conversions {
+    /// The impls of the conversions.
+    impls: TokenStream2,
+    /// The `VastRepr::Repr` of the enum.
+    repr: TokenStream2,
+    /// For each variant, a const expression that evaluates to its value as `repr`.
+    values: Vec<TokenStream2>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "VastEnum can only be derived for enums",
+            ))
+        }
+    };
+
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            input,
+            "VastEnum can't be derived for enums without variants",
+        ));
+    }
+
+    let mut variants = Vec::with_capacity(data.variants.len());
+    let mut literals = Vec::with_capacity(data.variants.len());
+    let mut names: Vec<LitStr> = Vec::with_capacity(data.variants.len());
+    let mut seen_names = HashSet::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "VastEnum can only be derived for fieldless enums",
+            ));
+        }
+        let attrs = variant_attrs(variant)?;
+        let name = attrs
+            .rename
+            .unwrap_or_else(|| LitStr::new(&variant.ident.to_string(), variant.ident.span()));
+        if !seen_names.insert(name.value()) {
+            return Err(Error::new_spanned(name, "duplicate variant name"));
+        }
+
+        variants.push(&variant.ident);
+        literals.push(attrs.value);
+        names.push(name);
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let Conversions {
+        impls: conversions,
+        repr: vast_repr,
+        values: variant_values,
+    } = if literals.iter().any(Option::is_some) {
+        literal_conversions(input, &variants, &literals)?
+    } else {
+        integer_conversions(input, &variants)?
+    };
+
+    let alias = format_ident!("Vast{}", name);
+    let alias_doc = format!(
+        "A [`VastEnum`](::vast_enum::VastEnum) wrapping [`{}`].",
+        name
+    );
+    let consts_trait = format_ident!("{}Consts", alias);
+    let consts_trait_doc = format!("A constant for each variant of [`{}`].", name);
+    let variant_consts: Vec<_> = variants
+        .iter()
+        .map(|variant| Ident::new(&screaming_snake_case(&variant.to_string()), variant.span()))
+        .collect();
+    let variant_consts_docs: Vec<_> = variants
+        .iter()
+        .map(|variant| format!("[`{}::{}`] as a [`{}`].", name, variant, alias))
+        .collect();
+
+    Ok(quote! {
+        #conversions
+
+        impl ::core::convert::TryFrom<::vast_enum::VastEnum<#name, #vast_repr>> for #name {
+            type Error = ::vast_enum::InvalidDiscriminant<#vast_repr>;
+
+            fn try_from(
+                enum_: ::vast_enum::VastEnum<#name, #vast_repr>,
+            ) -> ::core::result::Result<Self, Self::Error> {
+                enum_.try_variant()
+            }
+        }
+
+        impl ::vast_enum::VastRepr for #name {
+            type Repr = #vast_repr;
+        }
+
+        impl ::vast_enum::Variants for #name {
+            const VARIANTS: &'static [Self] = &[#(#name::#variants),*];
+            const NAMES: &'static [&'static str] = &[#(#names),*];
+        }
+
+        #[doc = #alias_doc]
+        #vis type #alias = ::vast_enum::VastEnum<#name, #vast_repr>;
+
+        #[doc = #consts_trait_doc]
+        #vis trait #consts_trait {
+            #(
+                #[doc = #variant_consts_docs]
+                const #variant_consts: #alias;
+            )*
+        }
+
+        impl #consts_trait for #alias {
+            #(const #variant_consts: #alias = ::vast_enum::VastEnum::from_int(#variant_values);)*
+        }
+    })
+}
+
+/// Generates the tag enum, the `TaggedUnion` impl and the `Vast{Enum}` alias of an enum with data,
+/// whose variants are unit variants or have a single unnamed `Payload` field.
+fn expand_union(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "VastUnion can only be derived for enums",
+            ))
+        }
+    };
+
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            input,
+            "VastUnion can't be derived for enums without variants",
+        ));
+    }
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "VastUnion can't be derived for generic enums",
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let tag = format_ident!("{}Tag", name);
+    let tag_doc = format!("The tags of [`{}`].", name);
+    let tag_attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr") || attr.path().is_ident("vast_enum"));
+
+    let mut tag_variants = Vec::with_capacity(data.variants.len());
+    let mut decode_arms = Vec::with_capacity(data.variants.len());
+    let mut encode_arms = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let attrs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("vast_enum"));
+        let discriminant = variant
+            .discriminant
+            .as_ref()
+            .map(|(eq, expr)| quote!(#eq #expr));
+        let doc = format!("The tag of [`{}::{}`].", name, ident);
+        tag_variants.push(quote!(#[doc = #doc] #(#attrs)* #ident #discriminant));
+
+        match &variant.fields {
+            Fields::Unit => {
+                decode_arms.push(quote! {
+                    #tag::#ident if payload.is_empty() => ::core::option::Option::Some(#name::#ident),
+                    #tag::#ident => ::core::option::Option::None,
+                });
+                encode_arms.push(quote!(#name::#ident => {}));
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                decode_arms.push(quote! {
+                    #tag::#ident => ::vast_enum::Payload::decode(payload).map(#name::#ident),
+                });
+                encode_arms.push(quote! {
+                    #name::#ident(payload) => ::vast_enum::Payload::encode(payload, output),
+                });
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "VastUnion variants must be unit variants or have a single unnamed field",
+                ))
+            }
+        }
+    }
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+
+    let alias = format_ident!("Vast{}", name);
+    let alias_doc = format!(
+        "A [`VastUnion`](::vast_enum::VastUnion) wrapping [`{}`].",
+        name
+    );
+
+    Ok(quote! {
+        #[doc = #tag_doc]
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::marker::Copy,
+            ::core::clone::Clone,
+            ::core::cmp::Eq,
+            ::core::cmp::PartialEq,
+            ::core::hash::Hash,
+            ::vast_enum::VastEnum,
+        )]
+        #(#tag_attrs)*
+        #vis enum #tag {
+            #(#tag_variants),*
+        }
+
+        impl ::vast_enum::TaggedUnion for #name {
+            type Tag = #tag;
+
+            fn tag(&self) -> #tag {
+                match self {
+                    #(#name::#variants { .. } => #tag::#variants,)*
+                }
+            }
+
+            fn decode_payload(tag: #tag, payload: &[u8]) -> ::core::option::Option<Self> {
+                match tag {
+                    #(#decode_arms)*
+                }
+            }
+
+            fn encode_payload(&self, output: &mut ::vast_enum::__Vec<u8>) {
+                match self {
+                    #(#encode_arms)*
+                }
+            }
+        }
+
+        #[doc = #alias_doc]
+        #vis type #alias<'a> = ::vast_enum::VastUnion<'a, #name>;
+    })
+}
+
+/// Generates the conversions of an enum whose variants are declared with discriminants, through
+/// its `#[repr(..)]` and optionally a narrow repr.
+fn integer_conversions(input: &DeriveInput, variants: &[&Ident]) -> syn::Result<Conversions> {
+    let repr = repr(input)?;
+    let narrow = narrow_repr(input)?;
+    let name = &input.ident;
+
+    let narrow_impls = narrow.as_ref().map(|narrow| {
+        let fit_errors: Vec<_> = variants
+            .iter()
+            .map(|variant| {
+                format!(
+                    "the discriminant of `{}::{}` doesn't fit in `{}`",
+                    name,
+                    variant,
+                    quote!(#narrow)
+                )
+            })
+            .collect();
+
+        quote! {
+            const _: () = {
+                let min: #repr = <#narrow as ::vast_enum::NarrowRepr>::MIN;
+                let max: #repr = <#narrow as ::vast_enum::NarrowRepr>::MAX;
+                #(
+                    ::core::assert!(
+                        min <= #name::#variants as #repr && #name::#variants as #repr <= max,
+                        #fit_errors
+                    );
+                )*
+            };
+
+            impl ::core::convert::From<#name> for #narrow {
+                fn from(enum_: #name) -> Self {
+                    match ::vast_enum::NarrowRepr::try_from_storage(enum_ as #repr) {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => ::core::unreachable!(),
+                    }
+                }
+            }
+
+            impl ::core::convert::TryFrom<#narrow> for #name {
+                type Error = ::vast_enum::InvalidDiscriminant<#narrow>;
+
+                fn try_from(value: #narrow) -> ::core::result::Result<Self, Self::Error> {
+                    let storage: #repr = ::vast_enum::NarrowRepr::storage(value);
+                    <Self as ::core::convert::TryFrom<#repr>>::try_from(storage)
+                        .map_err(|_| ::vast_enum::InvalidDiscriminant::new::<Self>(value))
+                }
+            }
+        }
+    });
+    let vast_repr = match &narrow {
+        Some(narrow) => quote!(#narrow),
+        None => quote!(#repr),
+    };
+    let values = variants
+        .iter()
+        .map(|variant| match &narrow {
+            // Trait methods can't be called in constants, so the value comes from an associated
+            // constant, which also works for type aliases. The range was checked above.
+            Some(narrow) => quote! {
+                <#narrow as ::vast_enum::NarrowConst<{ #name::#variant as #repr as u128 }>>::VALUE
+            },
+            None => quote!(#name::#variant as #repr),
+        })
+        .collect();
+
+    // Constants, so the discriminants can be matched on as patterns and rustc builds a switch.
+    let discriminants: Vec<_> = variants
+        .iter()
+        .map(|variant| format_ident!("__{}", screaming_snake_case(&variant.to_string())))
+        .collect();
+    let first = variants[0];
+
+    let impls = quote! {
+        impl ::core::convert::From<#name> for #repr {
+            fn from(enum_: #name) -> Self {
+                enum_ as Self
+            }
+        }
+
+        impl ::core::convert::TryFrom<#repr> for #name {
+            type Error = ::vast_enum::InvalidDiscriminant<#repr>;
+
+            fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+                #(const #discriminants: #repr = #name::#variants as #repr;)*
+
+                if !<Self as ::vast_enum::ValidityTable<#repr>>::DISCRIMINANTS.contains(value) {
+                    return ::core::result::Result::Err(::vast_enum::InvalidDiscriminant::new::<Self>(value));
+                }
+
+                // The table only holds discriminants, so the last arm is never taken. Since the
+                // match can't fail, `is_valid` compiles down to the table lookup.
+                ::core::result::Result::Ok(match value {
+                    #(#discriminants => #name::#variants,)*
+                    _ => #name::#first,
+                })
+            }
+        }
+
+        impl ::vast_enum::ValidityTable<#repr> for #name {
+            const DISCRIMINANTS: ::vast_enum::DiscriminantSet<#repr> =
+                ::vast_enum::__discriminant_set!(#repr; #(#name::#variants as #repr),*);
+        }
+
+        #narrow_impls
+    };
+
+    Ok(Conversions {
+        impls,
+        repr: vast_repr,
+        values,
+    })
+}
+
+/// Generates the conversions of an enum whose variants are declared with
+/// `#[vast_enum(value = ..)]` literals, by matching on the literals.
+fn literal_conversions(
+    input: &DeriveInput,
+    variants: &[&Ident],
+    literals: &[Option<Lit>],
+) -> syn::Result<Conversions> {
+    let name = &input.ident;
+    if let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("vast_enum"))
+    {
+        return Err(Error::new_spanned(
+            attr,
+            "`#[vast_enum(repr = ..)]` can't be combined with `#[vast_enum(value = ..)]`",
+        ));
+    }
+
+    let mut literals_checked = Vec::with_capacity(literals.len());
+    for (variant, literal) in variants.iter().zip(literals) {
+        let literal = match literal {
+            Some(literal) => literal,
+            None => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "every variant needs a `#[vast_enum(value = ..)]` if any variant has one",
+                ))
+            }
+        };
+        literals_checked.push(literal);
+    }
+
+    let first = literals_checked[0];
+    let (repr, patterns, values): (_, Vec<_>, Vec<_>) = match first {
+        Lit::Char(_) => {
+            let mut patterns = Vec::with_capacity(literals_checked.len());
+            let mut seen = Vec::with_capacity(literals_checked.len());
+            for literal in &literals_checked {
+                match literal {
+                    Lit::Char(c) if seen.contains(&c.value()) => return Err(duplicate(literal)),
+                    Lit::Char(c) => {
+                        seen.push(c.value());
+                        patterns.push(quote!(#c));
+                    }
+                    _ => return Err(mixed_literals(literal)),
+                }
+            }
+            let values = patterns.clone();
+            (quote!(char), patterns, values)
+        }
+        Lit::Byte(_) => {
+            let mut patterns = Vec::with_capacity(literals_checked.len());
+            let mut seen = Vec::with_capacity(literals_checked.len());
+            for literal in &literals_checked {
+                match literal {
+                    Lit::Byte(byte) if !byte.value().is_ascii() => {
+                        return Err(Error::new_spanned(byte, "byte literals must be ASCII"))
+                    }
+                    Lit::Byte(byte) if seen.contains(&byte.value()) => {
+                        return Err(duplicate(literal))
+                    }
+                    Lit::Byte(byte) => {
+                        seen.push(byte.value());
+                        patterns.push(quote!(#byte));
+                    }
+                    _ => return Err(mixed_literals(literal)),
+                }
+            }
+            let values = patterns
+                .iter()
+                .map(|byte| quote!(::vast_enum::AsciiByte::new(#byte)))
+                .collect();
+            (quote!(::vast_enum::AsciiByte), patterns, values)
+        }
+        Lit::ByteStr(first_bytes) => {
+            let len = first_bytes.value().len();
+            let mut patterns = Vec::with_capacity(literals_checked.len());
+            let mut seen = Vec::with_capacity(literals_checked.len());
+            for literal in &literals_checked {
+                match literal {
+                    Lit::ByteStr(bytes) if bytes.value().len() != len => {
+                        return Err(Error::new_spanned(
+                            bytes,
+                            format!(
+                                "expected a byte string of length {}, like the first variant",
+                                len
+                            ),
+                        ))
+                    }
+                    Lit::ByteStr(bytes) if seen.contains(&bytes.value()) => {
+                        return Err(duplicate(literal))
+                    }
+                    Lit::ByteStr(bytes) => {
+                        seen.push(bytes.value());
+                        patterns.push(quote!(#bytes));
+                    }
+                    _ => return Err(mixed_literals(literal)),
+                }
+            }
+            let values = patterns
+                .iter()
+                .map(|bytes| quote!(::vast_enum::ByteTag::new(*#bytes)))
+                .collect();
+            (quote!(::vast_enum::ByteTag<#len>), patterns, values)
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                first,
+                "expected a character, byte or byte-string literal",
+            ))
+        }
+    };
+    let key = match first {
+        Lit::Byte(_) => quote!(::vast_enum::AsciiByte::get(value)),
+        Lit::ByteStr(_) => quote!(::vast_enum::ByteTag::as_bytes(&value)),
+        _ => quote!(value),
+    };
+
+    let impls = quote! {
+        impl ::core::convert::From<#name> for #repr {
+            fn from(enum_: #name) -> Self {
+                match enum_ {
+                    #(#name::#variants => #values,)*
+                }
+            }
+        }
+
+        impl ::core::convert::TryFrom<#repr> for #name {
+            type Error = ::vast_enum::InvalidDiscriminant<#repr>;
+
+            fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+                match #key {
+                    #(#patterns => ::core::result::Result::Ok(#name::#variants),)*
+                    _ => ::core::result::Result::Err(::vast_enum::InvalidDiscriminant::new::<Self>(value)),
+                }
+            }
+        }
+    };
+
+    Ok(Conversions {
+        impls,
+        repr,
+        values,
+    })
+}
+
+fn duplicate(literal: &Lit) -> Error {
+    Error::new_spanned(literal, "duplicate variant value")
+}
+
+fn mixed_literals(literal: &Lit) -> Error {
+    Error::new_spanned(
+        literal,
+        "every variant value must be the same kind of literal",
+    )
+}
+
+/// Converts a `CamelCase` variant name to `SCREAMING_SNAKE_CASE`.
+fn screaming_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 && chars[i - 1] != '_' {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_uppercase());
+    }
+
+    out
+}
+
+/// Finds the primitive integer type in the enum's `#[repr(..)]` attribute.
+fn repr(input: &DeriveInput) -> syn::Result<Ident> {
+    let mut repr = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INTEGER_REPRS.iter().any(|int| ident == int) {
+                    repr = Some(ident.clone());
+                }
+            }
+
+            // Skip the arguments of reprs such as `align(4)`.
+            if meta.input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+
+            Ok(())
+        })?;
+    }
+
+    repr.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "VastEnum requires a primitive integer repr, such as `#[repr(u8)]`",
+        )
+    })
+}
+
+/// Finds the type in the enum's `#[vast_enum(repr = ..)]` attribute, if there is one.
+fn narrow_repr(input: &DeriveInput) -> syn::Result<Option<Type>> {
+    let mut narrow = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("vast_enum"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("repr") {
+                narrow = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported vast_enum attribute"))
+            }
+        })?;
+    }
+
+    Ok(narrow)
+}
+
+/// The `#[vast_enum(..)]` attributes of a variant.
+#[derive(Default)]
+struct VariantAttrs {
+    /// The literal from `value = ..`.
+    value: Option<Lit>,
+    /// The name from `rename = ".."`.
+    rename: Option<LitStr>,
+}
+
+/// Parses a variant's `#[vast_enum(value = .., rename = "..")]` attributes.
+fn variant_attrs(variant: &Variant) -> syn::Result<VariantAttrs> {
+    let mut attrs = VariantAttrs::default();
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("vast_enum"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("value") {
+                attrs.value = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                attrs.rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported vast_enum attribute"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}